@@ -0,0 +1,274 @@
+use chrono::{DateTime, Datelike, Utc};
+use regex::Regex;
+
+use crate::error::{Error, Result};
+
+/// Default format of the calendar versions.
+pub const DEFAULT_CALVER_FORMAT: &str = "YYYY.0M.MICRO";
+
+/// Part of a calendar version format.
+///
+/// See <https://calver.org/#scheme> for the meaning of the tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// Full year, e.g. `2006`.
+    FullYear,
+    /// Short year, e.g. `6`, `16`.
+    ShortYear,
+    /// Zero-padded year, e.g. `06`, `16`.
+    PaddedYear,
+    /// Short month, e.g. `1`, `11`.
+    ShortMonth,
+    /// Zero-padded month, e.g. `01`, `11`.
+    PaddedMonth,
+    /// Short ISO week of the year, e.g. `1`, `33`.
+    ShortWeek,
+    /// Zero-padded ISO week of the year, e.g. `01`, `33`.
+    PaddedWeek,
+    /// Short day of the month, e.g. `1`, `31`.
+    ShortDay,
+    /// Zero-padded day of the month, e.g. `01`, `31`.
+    PaddedDay,
+    /// Number that is incremented for each release within the same period.
+    Micro,
+    /// Literal text, e.g. `.`.
+    Literal(String),
+}
+
+impl Token {
+    /// Names of the tokens in the format string.
+    ///
+    /// The longer names come first to avoid partial matches.
+    const NAMES: &[(&str, Token)] = &[
+        ("YYYY", Token::FullYear),
+        ("MICRO", Token::Micro),
+        ("YY", Token::ShortYear),
+        ("0Y", Token::PaddedYear),
+        ("MM", Token::ShortMonth),
+        ("0M", Token::PaddedMonth),
+        ("WW", Token::ShortWeek),
+        ("0W", Token::PaddedWeek),
+        ("DD", Token::ShortDay),
+        ("0D", Token::PaddedDay),
+    ];
+
+    /// Returns the regex pattern that matches the token.
+    fn pattern(&self) -> String {
+        match self {
+            Self::FullYear => String::from(r"(\d{4})"),
+            Self::ShortYear => String::from(r"(\d{1,3})"),
+            Self::PaddedYear => String::from(r"(\d{2,3})"),
+            Self::ShortMonth | Self::ShortWeek | Self::ShortDay => String::from(r"(\d{1,2})"),
+            Self::PaddedMonth | Self::PaddedWeek | Self::PaddedDay => String::from(r"(\d{2})"),
+            Self::Micro => String::from(r"(\d+)"),
+            Self::Literal(text) => regex::escape(text),
+        }
+    }
+
+    /// Returns the value of the token for the given date and micro number.
+    ///
+    /// Returns `None` for literals.
+    fn value(&self, date: &DateTime<Utc>, micro: u64) -> Option<u64> {
+        let value = match self {
+            Self::FullYear => date.year(),
+            Self::ShortYear | Self::PaddedYear => date.year() - 2000,
+            Self::ShortMonth | Self::PaddedMonth => date.month() as i32,
+            Self::ShortWeek | Self::PaddedWeek => date.iso_week().week() as i32,
+            Self::ShortDay | Self::PaddedDay => date.day() as i32,
+            Self::Micro => return Some(micro),
+            Self::Literal(_) => return None,
+        };
+        Some(u64::try_from(value).unwrap_or_default())
+    }
+
+    /// Renders the token with the given value.
+    fn render(&self, value: u64) -> String {
+        match self {
+            Self::PaddedYear | Self::PaddedMonth | Self::PaddedWeek | Self::PaddedDay => {
+                format!("{value:02}")
+            }
+            Self::Literal(text) => text.clone(),
+            _ => value.to_string(),
+        }
+    }
+}
+
+/// Calendar versioning format, e.g. `YYYY.0M.MICRO`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalverFormat {
+    /// Parts of the format.
+    tokens: Vec<Token>,
+}
+
+impl CalverFormat {
+    /// Parses the given format string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format does not contain any date token.
+    pub fn parse(format: &str) -> Result<Self> {
+        let mut tokens = Vec::new();
+        let mut rest = format;
+        while let Some(c) = rest.chars().next() {
+            if let Some((name, token)) =
+                Token::NAMES.iter().find(|(name, _)| rest.starts_with(name))
+            {
+                tokens.push(token.clone());
+                rest = &rest[name.len()..];
+            } else {
+                if let Some(Token::Literal(text)) = tokens.last_mut() {
+                    text.push(c);
+                } else {
+                    tokens.push(Token::Literal(c.to_string()));
+                }
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        if !tokens
+            .iter()
+            .any(|token| !matches!(token, Token::Micro | Token::Literal(_)))
+        {
+            return Err(Error::ChangelogError(format!(
+                "Calendar version format does not contain a date: {format}"
+            )));
+        }
+        Ok(Self { tokens })
+    }
+
+    /// Renders the version for the given date and micro number.
+    #[must_use]
+    pub fn render(&self, date: &DateTime<Utc>, micro: u64) -> String {
+        self.format_version("", &self.values(date, micro))
+    }
+
+    /// Returns the values of the date and micro tokens.
+    fn values(&self, date: &DateTime<Utc>, micro: u64) -> Vec<u64> {
+        self.tokens
+            .iter()
+            .filter_map(|token| token.value(date, micro))
+            .collect()
+    }
+
+    /// Formats the version with the given prefix and values of the date and
+    /// micro tokens.
+    #[must_use]
+    pub fn format_version(&self, prefix: &str, values: &[u64]) -> String {
+        let mut values = values.iter();
+        let mut version = prefix.to_string();
+        for token in &self.tokens {
+            let value = match token {
+                Token::Literal(_) => 0,
+                _ => values.next().copied().unwrap_or_default(),
+            };
+            version.push_str(&token.render(value));
+        }
+        version
+    }
+
+    /// Parses the given version into its prefix (e.g. `v`) and the values of
+    /// the date and micro tokens.
+    ///
+    /// Returns `None` if the version does not match the format.
+    #[must_use]
+    pub fn parse_version(&self, version: &str) -> Option<(String, Vec<u64>)> {
+        let pattern = self.tokens.iter().map(Token::pattern).collect::<String>();
+        let regex = Regex::new(&format!("^(.*?){pattern}$")).ok()?;
+        let captures = regex.captures(version)?;
+        let values = captures
+            .iter()
+            .skip(2)
+            .map(|value| value?.as_str().parse().ok())
+            .collect::<Option<Vec<u64>>>()?;
+        Some((captures.get(1)?.as_str().to_string(), values))
+    }
+
+    /// Calculates the next version for the given date.
+    ///
+    /// If the previous version belongs to the same period, the micro number is
+    /// incremented. Otherwise, the micro number starts from `0`. The prefix of
+    /// the previous version (e.g. `v`) is preserved.
+    #[must_use]
+    pub fn next_version(&self, previous: Option<&str>, date: &DateTime<Utc>) -> String {
+        let Some(previous) = previous else {
+            return self.render(date, 0);
+        };
+        let Some((prefix, previous_values)) = self.parse_version(previous) else {
+            let prefix = previous
+                .chars()
+                .take_while(|c| !c.is_ascii_digit())
+                .collect::<String>();
+            return self.format_version(&prefix, &self.values(date, 0));
+        };
+        let micro_index = self
+            .tokens
+            .iter()
+            .filter(|token| !matches!(token, Token::Literal(_)))
+            .position(|token| token == &Token::Micro);
+        let mut values = self.values(date, 0);
+        let same_period = values
+            .iter()
+            .zip(&previous_values)
+            .enumerate()
+            .all(|(i, (value, previous))| Some(i) == micro_index || value == previous);
+        if let (true, Some(index)) = (same_period, micro_index) {
+            values[index] = previous_values[index] + 1;
+        }
+        self.format_version(&prefix, &values)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn render_calver_format() -> Result<()> {
+        let date = Utc.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap();
+        for (format, expected) in [
+            ("YYYY.MM.MICRO", "2026.1.3"),
+            ("YYYY.0M.MICRO", "2026.01.3"),
+            ("YY.0W", "26.02"),
+            ("0Y.WW.DD", "26.2.5"),
+            ("YYYY-0M-0D_MICRO", "2026-01-05_3"),
+        ] {
+            assert_eq!(expected, CalverFormat::parse(format)?.render(&date, 3));
+        }
+        assert!(CalverFormat::parse("MICRO").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn next_calver_version() -> Result<()> {
+        let date = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let format = CalverFormat::parse("YYYY.MM.MICRO")?;
+        for (previous, expected) in [
+            (None, "2026.10.0"),
+            (Some("2026.10.0"), "2026.10.1"),
+            (Some("v2026.10.4"), "v2026.10.5"),
+            (Some("v2026.9.4"), "v2026.10.0"),
+            (Some("v1.2.3"), "v2026.10.0"),
+        ] {
+            assert_eq!(expected, format.next_version(previous, &date));
+        }
+
+        let format = CalverFormat::parse("YY.0W")?;
+        assert_eq!("26.42", format.next_version(Some("26.41"), &date));
+        assert_eq!("26.42", format.next_version(Some("26.42"), &date));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_calver_version() -> Result<()> {
+        let format = CalverFormat::parse("YYYY.0M.MICRO")?;
+        assert_eq!(
+            Some((String::from("v"), vec![2026, 9, 3])),
+            format.parse_version("v2026.09.3")
+        );
+        assert_eq!(None, format.parse_version("v1.2"));
+        assert_eq!("v2026.09.3", format.format_version("v", &[2026, 9, 3]));
+        Ok(())
+    }
+}
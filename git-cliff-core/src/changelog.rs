@@ -1,11 +1,18 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::io::{Read, Write};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::config::Config;
+use crate::command;
+use crate::commit::{Commit, Range};
+use crate::config::{Config, PluginStage};
 use crate::error::Result;
 use crate::process::CommitProcessor;
-use crate::release::{Release, Releases};
+use crate::release::{
+    DependencyUpdate, ExternalRelease, NextVersion, Prerelease, Release, Releases,
+};
 #[cfg(feature = "azure_devops")]
 use crate::remote::azure_devops::AzureDevOpsClient;
 #[cfg(feature = "bitbucket")]
@@ -18,12 +25,21 @@ use crate::remote::github::GitHubClient;
 use crate::remote::gitlab::GitLabClient;
 use crate::summary::Summary;
 use crate::template::Template;
+use crate::version::{ParsedVersion, ReleaseLine, VersionScheme};
+
+/// Marker in the tag message for yanked releases.
+const YANKED_MARKER: &str = "[YANKED]";
+
+/// Default template for the message of the release commit.
+const DEFAULT_COMMIT_MESSAGE: &str = "chore(release): {{ version }}";
 
 /// Changelog generator.
 #[derive(Debug)]
 pub struct Changelog<'a> {
     /// Releases that the changelog will contain.
     pub releases: Vec<Release<'a>>,
+    /// Releases beyond the limit that are written to the archive.
+    pub archived_releases: Vec<Release<'a>>,
     /// Configuration used for generating the changelog.
     pub config: Config,
     header_template: Option<Template>,
@@ -34,7 +50,25 @@ pub struct Changelog<'a> {
 
 impl<'a> Changelog<'a> {
     /// Constructs a new instance.
+    ///
+    /// The commits of the releases are expected to be sorted according to
+    /// `git.sort_commits`.
     pub fn new(releases: Vec<Release<'a>>, config: Config, range: Option<&str>) -> Result<Self> {
+        let newest_first = config.git.sort_commits.eq_ignore_ascii_case("newest");
+        Self::with_commit_order(releases, config, range, newest_first)
+    }
+
+    /// Constructs a new instance from releases with the commits sorted in the
+    /// given order.
+    ///
+    /// This is used when the commit order is overridden (e.g. via `--sort`)
+    /// instead of being taken from `git.sort_commits`.
+    pub fn with_commit_order(
+        releases: Vec<Release<'a>>,
+        config: Config,
+        range: Option<&str>,
+        newest_first: bool,
+    ) -> Result<Self> {
         let is_offline = config.remote.offline;
         let mut changelog = Changelog::build(releases, config)?;
 
@@ -44,8 +78,14 @@ impl<'a> Changelog<'a> {
             changelog.add_remote_data(range)?;
         }
 
+        changelog.run_after_fetch_plugins()?;
         changelog.process_commits()?;
-        changelog.process_releases();
+        changelog.collapse_prereleases(newest_first)?;
+        changelog.process_releases()?;
+        changelog.mark_yanked_releases();
+        let releases = std::mem::take(&mut changelog.releases);
+        changelog.releases = changelog.run_plugins(releases, PluginStage::AfterProcessing)?;
+        changelog.archive_releases()?;
         Ok(changelog)
     }
 
@@ -65,15 +105,24 @@ impl<'a> Changelog<'a> {
             String::from("commit_parsers_groups"),
             serde_json::to_value(parser_groups)?,
         );
+        let version_scheme: Arc<dyn VersionScheme> = Arc::from(config.bump.version_scheme()?);
+        let template = |name: &str, content: &str| -> Result<Template> {
+            let mut template = Template::new(name, content.to_string(), trim)?;
+            template.set_version_scheme(Arc::clone(&version_scheme));
+            Ok(template)
+        };
+        let mut body_template = get_body_template(&config, trim)?;
+        body_template.set_version_scheme(Arc::clone(&version_scheme));
         let changelog = Self {
             releases,
+            archived_releases: Vec::new(),
             header_template: match &config.changelog.header {
-                Some(header) => Some(Template::new("header", header.clone(), trim)?),
+                Some(header) => Some(template("header", header)?),
                 None => None,
             },
-            body_template: get_body_template(&config, trim)?,
+            body_template,
             footer_template: match &config.changelog.footer {
-                Some(footer) => Some(Template::new("footer", footer.clone(), trim)?),
+                Some(footer) => Some(template("footer", footer)?),
                 None => None,
             },
             config,
@@ -85,7 +134,10 @@ impl<'a> Changelog<'a> {
 
     /// Constructs an instance from a serialized context object.
     pub fn from_context<R: Read>(input: &mut R, config: Config) -> Result<Self> {
-        Changelog::build(serde_json::from_reader(input)?, config)
+        let mut changelog = Changelog::build(serde_json::from_reader(input)?, config)?;
+        changelog.mark_yanked_releases();
+        changelog.archive_releases()?;
+        Ok(changelog)
     }
 
     /// Adds a key value pair to the template context.
@@ -105,6 +157,56 @@ impl<'a> Changelog<'a> {
         Ok(())
     }
 
+    /// Runs the plugins of the given stage on the releases and returns the
+    /// modified releases.
+    ///
+    /// The releases are passed to the plugins as [`ExternalRelease`]s and only
+    /// their editable fields are read back. The releases are returned as is
+    /// if there are no plugins for the stage.
+    fn run_plugins(
+        &self,
+        mut releases: Vec<Release<'a>>,
+        stage: PluginStage,
+    ) -> Result<Vec<Release<'a>>> {
+        for plugin in self.config.plugins.iter().filter(|v| v.stage == stage) {
+            crate::set_progress_message!("Running the {stage} plugin: {}", plugin.command);
+            tracing::debug!("Running the {stage} plugin: {}", plugin.command);
+            let input = serde_json::to_string(
+                &releases
+                    .iter()
+                    .map(ExternalRelease::from)
+                    .collect::<Vec<_>>(),
+            )?;
+            let output = command::run(&plugin.command, Some(input), vec![(
+                "GIT_CLIFF_PLUGIN_STAGE",
+                &stage.to_string(),
+            )])?;
+            releases = ExternalRelease::apply(releases, serde_json::from_str(&output)?)?;
+        }
+        Ok(releases)
+    }
+
+    /// Runs the plugins that are configured to run after fetching the commits.
+    ///
+    /// The commits are not processed at this stage, so the `message` field is
+    /// used as the commit message when processing.
+    fn run_after_fetch_plugins(&mut self) -> Result<()> {
+        if !self
+            .config
+            .plugins
+            .iter()
+            .any(|v| v.stage == PluginStage::AfterFetch)
+        {
+            return Ok(());
+        }
+        // Releases are passed in the same order as the changelog context.
+        let mut releases = std::mem::take(&mut self.releases);
+        releases.reverse();
+        self.releases = self.run_plugins(releases, PluginStage::AfterFetch)?;
+        self.releases.reverse();
+        Ok(())
+    }
+
     /// Processes the commits and omits the ones that doesn't match the
     /// criteria set by configuration file.
     #[cfg_attr(
@@ -147,6 +249,92 @@ impl<'a> Changelog<'a> {
         Ok(())
     }
 
+    /// Merges the pre-releases into the next stable release of the same
+    /// version if `collapse_prereleases` is enabled.
+    ///
+    /// The merged release contains the commits of the pre-releases and the
+    /// pre-releases are kept in its `prereleases` field. Pre-releases that are
+    /// not followed by a stable release of the same version are not changed.
+    /// `newest_first` is the order of the commits in the releases.
+    fn collapse_prereleases(&mut self, newest_first: bool) -> Result<()> {
+        if !self.config.git.collapse_prereleases {
+            return Ok(());
+        }
+        crate::set_progress_message!("Collapsing pre-releases");
+        let version_scheme = self.config.bump.version_scheme()?;
+        let mut releases = Vec::new();
+        let mut prereleases: Vec<Release<'a>> = Vec::new();
+        let mut prerelease_base: Option<ParsedVersion> = None;
+        for mut release in std::mem::take(&mut self.releases) {
+            let version = release
+                .version
+                .as_deref()
+                .and_then(|version| version_scheme.parse(version));
+            let base = version.as_ref().map(|version| ParsedVersion {
+                epoch: version.epoch,
+                release: version.release.clone(),
+                ..Default::default()
+            });
+            let same_version = matches!(
+                (&prerelease_base, &base),
+                (Some(a), Some(b)) if version_scheme.compare(a, b) == Ordering::Equal
+            );
+            if !same_version {
+                releases.append(&mut prereleases);
+            }
+            if version
+                .as_ref()
+                .is_some_and(|version| version.pre.is_some() || version.dev.is_some())
+            {
+                prerelease_base = base;
+                prereleases.push(release);
+                continue;
+            }
+            if !prereleases.is_empty() {
+                tracing::debug!(
+                    "Collapsing {} pre-release(s) into {}",
+                    prereleases.len(),
+                    release.version.as_deref().unwrap_or_default()
+                );
+                let mut commits = Vec::new();
+                let mut submodule_commits: HashMap<String, Vec<Commit<'a>>> = HashMap::new();
+                let merged = if newest_first {
+                    let mut merged = vec![release.clone()];
+                    merged.extend(prereleases.iter().rev().cloned());
+                    merged
+                } else {
+                    let mut merged = prereleases.clone();
+                    merged.push(release.clone());
+                    merged
+                };
+                for merged_release in merged {
+                    commits.extend(merged_release.commits);
+                    for (path, path_commits) in merged_release.submodule_commits {
+                        submodule_commits
+                            .entry(path)
+                            .or_default()
+                            .extend(path_commits);
+                    }
+                }
+                release.commit_range = match (commits.first(), commits.last()) {
+                    (Some(first), Some(last)) if newest_first => Some(Range::new(last, first)),
+                    (Some(first), Some(last)) => Some(Range::new(first, last)),
+                    _ => None,
+                };
+                release.commits = commits;
+                release.submodule_commits = submodule_commits;
+                release.previous = prereleases[0].previous.clone();
+                release.prereleases = prereleases.iter().map(Prerelease::from).collect();
+                prereleases.clear();
+            }
+            prerelease_base = None;
+            releases.push(release);
+        }
+        releases.append(&mut prereleases);
+        self.releases = releases;
+        Ok(())
+    }
+
     /// Processes the releases and filters them out based on the configuration.
     #[cfg_attr(
         feature = "tracing",
@@ -155,10 +343,17 @@ impl<'a> Changelog<'a> {
             fields(releases = self.releases.len())
         )
     )]
-    fn process_releases(&mut self) {
+    fn process_releases(&mut self) -> Result<()> {
         crate::set_progress_message!("Processing releases for the changelog");
         tracing::debug!("Processing {} release(s)", self.releases.len());
         let skip_regex = self.config.git.skip_tags.as_ref();
+        let release_line = self
+            .config
+            .git
+            .release_line
+            .as_deref()
+            .and_then(ReleaseLine::parse);
+        let version_scheme = self.config.bump.version_scheme()?;
         let mut skipped_tags = Vec::new();
         self.releases = self
             .releases
@@ -167,7 +362,11 @@ impl<'a> Changelog<'a> {
             .rev()
             .filter(|release| {
                 if let Some(version) = &release.version {
-                    if skip_regex.is_some_and(|r| r.is_match(version)) {
+                    if skip_regex.is_some_and(|r| r.is_match(version)) ||
+                        release_line.is_some_and(|line| {
+                            !line.contains_tag(version, version_scheme.as_ref())
+                        })
+                    {
                         skipped_tags.push(version.clone());
                         tracing::debug!("Skipping release: {version}");
                         return false;
@@ -205,6 +404,60 @@ impl<'a> Changelog<'a> {
                 }
             }
         }
+        Ok(())
+    }
+
+    /// Marks the releases that are yanked.
+    ///
+    /// A release is yanked if its version is listed in the configuration or
+    /// its tag message contains the yanked marker.
+    fn mark_yanked_releases(&mut self) {
+        for release in &mut self.releases {
+            let yanked = release
+                .version
+                .as_deref()
+                .is_some_and(|version| self.config.changelog.is_yanked(version)) ||
+                release
+                    .message
+                    .as_deref()
+                    .is_some_and(|message| message.contains(YANKED_MARKER));
+            if yanked {
+                tracing::debug!("Release is yanked: {:?}", release.version);
+                release.yanked = true;
+            }
+        }
+    }
+
+    /// Moves the releases beyond the configured limit to the archive.
+    ///
+    /// The unreleased changes are not counted towards the limit. If there
+    /// are archived releases, the link to the archive is added to the
+    /// template context as `archive_link`.
+    fn archive_releases(&mut self) -> Result<()> {
+        let Some(limit) = self.config.changelog.limit_releases else {
+            return Ok(());
+        };
+        let unreleased = self
+            .releases
+            .iter()
+            .take_while(|release| release.version.is_none())
+            .count();
+        if self.releases.len() <= unreleased + limit {
+            return Ok(());
+        }
+        self.archived_releases = self.releases.split_off(unreleased + limit);
+        tracing::debug!("Archiving {} releases", self.archived_releases.len());
+        let archive_link = self.config.changelog.archive_link.clone().or_else(|| {
+            self.config
+                .changelog
+                .archive_output
+                .as_ref()
+                .map(|path| path.to_string_lossy().into_owned())
+        });
+        if let Some(archive_link) = archive_link {
+            self.add_context("archive_link", archive_link)?;
+        }
+        Ok(())
     }
 
     /// Returns the GitHub metadata needed for the changelog.
@@ -560,26 +813,114 @@ impl<'a> Changelog<'a> {
         Ok(())
     }
 
+    /// Records that a dependency is updated to the given version.
+    ///
+    /// The update is added to the unreleased changes, which are created if
+    /// there are none, so that the next version is bumped at least by a patch.
+    pub fn add_dependency_update(&mut self, name: impl Into<String>, version: impl Into<String>) {
+        if self
+            .releases
+            .first()
+            .is_none_or(|release| release.version.is_some())
+        {
+            let previous = self.releases.first().cloned().map(|mut release| {
+                release.previous = None;
+                Box::new(release)
+            });
+            let repository = previous
+                .as_ref()
+                .and_then(|release| release.repository.clone());
+            self.releases.insert(0, Release {
+                previous,
+                repository,
+                ..Default::default()
+            });
+        }
+        self.releases[0].dependency_updates.push(DependencyUpdate {
+            name: name.into(),
+            version: version.into(),
+        });
+    }
+
+    /// Calculates the next version for the unreleased changes without
+    /// bumping it.
+    ///
+    /// Returns `None` if there are no unreleased changes.
+    pub fn next_version(&self) -> Result<Option<NextVersion>> {
+        self.releases
+            .first()
+            .filter(|release| release.version.is_none())
+            .map(|release| release.calculate_next_version_with_config(&self.config.bump))
+            .transpose()
+    }
+
     /// Increments the version for the unreleased changes based on semver.
+    ///
+    /// Returns the next version along with the commits that triggered the
+    /// bump, or `None` if there are no unreleased changes.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    pub fn bump_version(&mut self) -> Result<Option<String>> {
+    pub fn bump_version(&mut self) -> Result<Option<NextVersion>> {
         crate::set_progress_message!("Bumping the version for unreleased changes");
-        if let Some(ref mut last_release) = self.releases.iter_mut().next() {
-            if last_release.version.is_none() {
-                let next = last_release.calculate_next_version_with_config(&self.config.bump)?;
-                tracing::debug!("Bumping the version to {}", next.version);
-                last_release.bump_type = next.bump_type;
-                last_release.version = Some(next.version.clone());
-                last_release.timestamp = Some(
-                    SystemTime::now()
-                        .duration_since(UNIX_EPOCH)?
-                        .as_secs()
-                        .try_into()?,
-                );
-                return Ok(Some(next.version));
-            }
+        let Some(next) = self.next_version()? else {
+            return Ok(None);
+        };
+        tracing::debug!("Bumping the version to {}", next.version);
+        let last_release = &mut self.releases[0];
+        last_release.bump_type = next.bump_type;
+        last_release.version = Some(next.version.clone());
+        last_release.timestamp = Some(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)?
+                .as_secs()
+                .try_into()?,
+        );
+        Ok(Some(next))
+    }
+
+    /// Renders the given template for the latest release.
+    fn render_latest_release(&self, name: &str, template: &str) -> Result<String> {
+        let Some(release) = self.releases.first() else {
+            return Ok(String::new());
+        };
+        let mut template = Template::new(name, template.to_string(), self.config.changelog.trim)?;
+        template.set_version_scheme(Arc::from(self.config.bump.version_scheme()?));
+        Ok(template
+            .render(release, Some(&self.additional_context), &[])?
+            .trim()
+            .to_string())
+    }
+
+    /// Renders the message of the tag for the latest release.
+    ///
+    /// The `bump.tag_message` template is used if it is set, otherwise the
+    /// changelog body template.
+    pub fn tag_message(&self) -> Result<String> {
+        match &self.config.bump.tag_message {
+            Some(tag_message) => self.render_latest_release("tag_message", tag_message),
+            None => Ok(self
+                .releases
+                .first()
+                .map(|release| {
+                    self.body_template
+                        .render(release, Some(&self.additional_context), &[])
+                })
+                .transpose()?
+                .unwrap_or_default()
+                .trim()
+                .to_string()),
         }
-        Ok(None)
+    }
+
+    /// Renders the message of the release commit for the latest release.
+    pub fn commit_message(&self) -> Result<String> {
+        self.render_latest_release(
+            "commit_message",
+            self.config
+                .bump
+                .commit_message
+                .as_deref()
+                .unwrap_or(DEFAULT_COMMIT_MESSAGE),
+        )
     }
 
     /// Generates the changelog and writes it to the given output.
@@ -587,7 +928,41 @@ impl<'a> Changelog<'a> {
     pub fn generate<W: Write + ?Sized>(&self, out: &mut W) -> Result<()> {
         crate::set_progress_message!("Generating and writing the changelog");
         tracing::debug!("Generating changelog");
+        self.render(&self.releases, &self.additional_context, out)
+    }
+
+    /// Generates the archive of the releases beyond the limit and writes it to
+    /// the given output.
+    ///
+    /// The archive is rendered with the same templates as the changelog,
+    /// without the `archive_link` in the context.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn generate_archive<W: Write + ?Sized>(&self, out: &mut W) -> Result<()> {
+        crate::set_progress_message!("Generating and writing the archived releases");
+        tracing::debug!("Generating archive");
+        let mut context = self.additional_context.clone();
+        context.remove("archive_link");
+        self.render(&self.archived_releases, &context, out)
+    }
+
+    /// Renders the given releases with the templates.
+    fn render<W: Write + ?Sized>(
+        &self,
+        releases: &Vec<Release<'a>>,
+        context: &HashMap<String, serde_json::Value>,
+        out: &mut W,
+    ) -> Result<()> {
         let postprocessors = self.config.changelog.postprocessors.clone();
+        let releases = if self
+            .config
+            .plugins
+            .iter()
+            .any(|v| v.stage == PluginStage::BeforeRender)
+        {
+            Cow::Owned(self.run_plugins(releases.clone(), PluginStage::BeforeRender)?)
+        } else {
+            Cow::Borrowed(releases)
+        };
 
         if let Some(header_template) = &self.header_template {
             let write_result = writeln!(
@@ -595,9 +970,9 @@ impl<'a> Changelog<'a> {
                 "{}",
                 header_template.render(
                     &Releases {
-                        releases: &self.releases,
+                        releases: &releases,
                     },
-                    Some(&self.additional_context),
+                    Some(context),
                     &postprocessors,
                 )?
             );
@@ -608,15 +983,12 @@ impl<'a> Changelog<'a> {
             }
         }
 
-        for release in &self.releases {
+        for release in releases.iter() {
             let write_result = write!(
                 out,
                 "{}",
-                self.body_template.render(
-                    &release,
-                    Some(&self.additional_context),
-                    &postprocessors
-                )?
+                self.body_template
+                    .render(&release, Some(context), &postprocessors)?
             );
             if let Err(e) = write_result {
                 if e.kind() != std::io::ErrorKind::BrokenPipe {
@@ -631,9 +1003,9 @@ impl<'a> Changelog<'a> {
                 "{}",
                 footer_template.render(
                     &Releases {
-                        releases: &self.releases,
+                        releases: &releases,
                     },
-                    Some(&self.additional_context),
+                    Some(context),
                     &postprocessors,
                 )?
             );
@@ -762,6 +1134,7 @@ fn get_body_template(config: &Config, trim: bool) -> Result<Template> {
 
 #[cfg(test)]
 mod test {
+    use std::path::PathBuf;
     use std::str;
 
     use pretty_assertions::assert_eq;
@@ -770,8 +1143,8 @@ mod test {
     use super::*;
     use crate::commit::{Commit, Signature};
     use crate::config::{
-        Bump, ChangelogConfig, CommitParser, GitConfig, LinkParser, Remote, RemoteConfig,
-        TextProcessor,
+        Bump, ChangelogConfig, CommitDate, CommitParser, GitConfig, LinkParser, Plugin,
+        ReleaseDate, Remote, RemoteConfig, TagSort, TextProcessor,
     };
 
     fn get_test_data() -> (Config, Vec<Release<'static>>) {
@@ -815,6 +1188,10 @@ mod test {
                 }],
                 render_always: false,
                 output: None,
+                yanked: vec![],
+                limit_releases: None,
+                archive_output: None,
+                archive_link: None,
             },
             git: GitConfig {
                 processing_order: None,
@@ -964,13 +1341,20 @@ mod test {
                 protect_breaking_commits: false,
                 filter_commits: false,
                 fail_on_unmatched_commit: false,
+                require_signed_commits: false,
                 tag_pattern: None,
                 skip_tags: Regex::new("v3.*").ok(),
                 ignore_tags: None,
                 count_tags: None,
+                collapse_prereleases: false,
                 use_branch_tags: false,
+                release_line: None,
                 topo_order: false,
+                tag_sort: TagSort::Time,
+                release_date: ReleaseDate::Commit,
+                commit_date: CommitDate::Committer,
                 topo_order_commits: true,
+                first_parent: false,
                 sort_commits: String::from("oldest"),
                 link_parsers: vec![LinkParser {
                     pattern: Regex::new("#(\\d+)").expect("issue reference regex should be valid"),
@@ -981,6 +1365,9 @@ mod test {
                 recurse_submodules: None,
                 include_paths: Vec::new(),
                 exclude_paths: Vec::new(),
+                include_authors: Vec::new(),
+                exclude_authors: Vec::new(),
+                mailmap_file: None,
             },
             remote: RemoteConfig {
                 offline: false,
@@ -1031,6 +1418,8 @@ mod test {
                 },
             },
             bump: Bump::default(),
+            plugins: Vec::new(),
+            packages: Vec::new(),
         };
         let test_release = Release {
             version: Some(String::from("v1.0.0")),
@@ -1198,6 +1587,14 @@ mod test {
             ])]),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: crate::remote::RemoteReleaseMetadata {
                 contributors: vec![],
@@ -1320,6 +1717,14 @@ mod test {
                 ]),
                 statistics: None,
                 bump_type: None,
+                signed: false,
+                signature_type: None,
+                signer_key: None,
+                yanked: false,
+                tagger: None,
+                tag_timestamp: None,
+                dependency_updates: Vec::new(),
+                prereleases: Vec::new(),
                 #[cfg(feature = "github")]
                 github: crate::remote::RemoteReleaseMetadata {
                     contributors: vec![],
@@ -1443,6 +1848,23 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn changelog_release_messages() -> Result<()> {
+        let (mut config, releases) = get_test_data();
+        config.bump.tag_message = Some(String::from(
+            "Release {{ version }}\n{% for commit in commits %}\n- {{ commit.message }}{% endfor \
+             %}\n",
+        ));
+        let mut changelog = Changelog::new(releases, config, None)?;
+        changelog.bump_version()?;
+        assert_eq!(
+            "Release v1.1.0\n\n- add xyz\n- document zyx\n- fix abc\n- do boring stuff",
+            changelog.tag_message()?
+        );
+        assert_eq!("chore(release): v1.1.0", changelog.commit_message()?);
+        Ok(())
+    }
+
     #[test]
     fn changelog_generator_render_always() -> Result<()> {
         let (mut config, mut releases) = get_test_data();
@@ -1477,6 +1899,85 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn changelog_adds_dependency_updates() -> Result<()> {
+        let (config, mut releases) = get_test_data();
+        releases.pop();
+        let mut changelog = Changelog::new(releases, config, None)?;
+        assert_eq!(Some("v1.0.0"), changelog.releases[0].version.as_deref());
+        assert_eq!(None, changelog.next_version()?);
+
+        changelog.add_dependency_update("core", "core-v0.2.0");
+        changelog.add_dependency_update("web", "web-v1.0.0");
+        let release = &changelog.releases[0];
+        assert_eq!(None, release.version);
+        assert!(release.commits.is_empty());
+        assert_eq!(
+            Some("v1.0.0"),
+            release.previous.as_ref().and_then(|p| p.version.as_deref())
+        );
+        assert_eq!(
+            vec![
+                DependencyUpdate {
+                    name: String::from("core"),
+                    version: String::from("core-v0.2.0"),
+                },
+                DependencyUpdate {
+                    name: String::from("web"),
+                    version: String::from("web-v1.0.0"),
+                }
+            ],
+            release.dependency_updates
+        );
+        assert_eq!(
+            Some("v1.0.1"),
+            changelog
+                .next_version()?
+                .as_ref()
+                .map(|next| next.version.as_str())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn changelog_generator_archive() -> Result<()> {
+        let (mut config, mut releases) = get_test_data();
+        config.changelog.header = None;
+        config.changelog.trim = false;
+        config.changelog.body = String::from("{{ version | default(value='unreleased') }}\n");
+        config.changelog.footer = Some(String::from(
+            "{% if archive_link %}[Older releases]({{ archive_link }}){% endif %}",
+        ));
+        config.changelog.limit_releases = Some(1);
+        config.changelog.archive_output = Some(PathBuf::from("CHANGELOG-2023.md"));
+        let mut release = releases[0].clone();
+        release.version = Some(String::from("v0.9.0"));
+        releases.insert(0, release);
+
+        let changelog = Changelog::new(releases.clone(), config.clone(), None)?;
+        let mut out = Vec::new();
+        changelog.generate(&mut out)?;
+        assert_eq!(
+            "unreleased\nv1.0.0\n[Older releases](CHANGELOG-2023.md)\n",
+            str::from_utf8(&out).unwrap_or_default()
+        );
+        let mut out = Vec::new();
+        changelog.generate_archive(&mut out)?;
+        assert_eq!("v0.9.0\n\n", str::from_utf8(&out).unwrap_or_default());
+
+        config.changelog.archive_link = Some(String::from("https://example.com/archive"));
+        config.changelog.limit_releases = Some(2);
+        let changelog = Changelog::new(releases, config, None)?;
+        assert!(changelog.archived_releases.is_empty());
+        let mut out = Vec::new();
+        changelog.generate(&mut out)?;
+        assert_eq!(
+            "unreleased\nv1.0.0\nv0.9.0\n\n",
+            str::from_utf8(&out).unwrap_or_default()
+        );
+        Ok(())
+    }
+
     #[test]
     fn changelog_generator_split_commits() -> Result<()> {
         let (mut config, mut releases) = get_test_data();
@@ -1662,6 +2163,10 @@ chore(deps): fix broken deps
                 postprocessors: Vec::new(),
                 render_always: false,
                 output: None,
+                yanked: vec![],
+                limit_releases: None,
+                archive_output: None,
+                archive_link: None,
             },
             git: GitConfig {
                 conventional_commits: true,
@@ -1692,6 +2197,8 @@ chore(deps): fix broken deps
             },
             remote: RemoteConfig::default(),
             bump: Bump::default(),
+            plugins: Vec::new(),
+            packages: Vec::new(),
         };
 
         // Commits arrive in an order whose group names sort alphabetically
@@ -1863,4 +2370,107 @@ chore(deps): fix broken deps
 
         Ok(())
     }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn changelog_runs_plugins() -> Result<()> {
+        let (mut config, releases) = get_test_data();
+        config.changelog.header = None;
+        config.changelog.body = String::from(
+            "{% for commit in commits %}- {{ commit.message }} ({{ commit.group }})\n{% endfor %}",
+        );
+        config.changelog.footer = None;
+        config.changelog.postprocessors = Vec::new();
+        config.plugins = vec![
+            Plugin {
+                command: String::from("sed 's/add xyz/add abc/g'"),
+                stage: PluginStage::AfterFetch,
+            },
+            Plugin {
+                command: String::from("sed 's/\"Other\"/\"Documentation\"/g'"),
+                stage: PluginStage::AfterProcessing,
+            },
+            Plugin {
+                command: String::from("sed \"s/fix abc/fix $GIT_CLIFF_PLUGIN_STAGE/g\""),
+                stage: PluginStage::BeforeRender,
+            },
+        ];
+
+        let changelog = Changelog::new(vec![releases[2].clone()], config, None)?;
+        let mut out = Vec::new();
+        changelog.generate(&mut out)?;
+        assert_eq!(
+            "- add abc (New features)\n- document zyx (Documentation)\n- fix before_render (Bug \
+             Fixes)\n- do boring stuff (Documentation)\n",
+            str::from_utf8(&out).unwrap_or_default()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn changelog_collapses_prereleases() -> Result<()> {
+        let (mut config, _) = get_test_data();
+        config.changelog.header = None;
+        config.changelog.body = String::from(
+            "{{ version }}[{% for prerelease in prereleases %}{{ prerelease.version }},{% endfor \
+             %}]({% if previous %}{{ previous.version }}{% endif %}):{% for commit in commits \
+             %}{{ commit.message }},{% endfor %}",
+        );
+        config.changelog.footer = None;
+        config.changelog.postprocessors = Vec::new();
+        config.git.commit_parsers = Vec::new();
+        config.git.filter_commits = false;
+        config.git.collapse_prereleases = true;
+
+        let mut releases: Vec<Release> = Vec::new();
+        for (version, message) in [
+            (Some("v1.0.0"), "feat: first"),
+            (Some("v2.0.0-rc.1"), "feat: second"),
+            (Some("v2.0.0-rc.2"), "fix: third"),
+            (Some("v2.0.0"), "fix: fourth"),
+            (Some("v2.1.0-rc.1"), "feat: fifth"),
+            (None, "fix: sixth"),
+        ] {
+            releases.push(Release {
+                version: version.map(String::from),
+                commits: vec![Commit::new(message.to_string(), message.to_string())],
+                previous: releases.last().cloned().map(|mut previous| {
+                    previous.previous = None;
+                    Box::new(previous)
+                }),
+                ..Default::default()
+            });
+        }
+
+        let newest_first =
+            Changelog::with_commit_order(releases.clone(), config.clone(), None, true)?;
+        assert_eq!(
+            vec!["fix: fourth", "fix: third", "feat: second"],
+            newest_first.releases[2]
+                .commits
+                .iter()
+                .map(|commit| commit.message.as_str())
+                .collect::<Vec<_>>()
+        );
+
+        let changelog = Changelog::new(releases, config, None)?;
+        let v2 = &changelog.releases[2];
+        assert_eq!(Some(String::from("v2.0.0")), v2.version);
+        assert_eq!(
+            Some(Range::new(&v2.commits[0], &v2.commits[2])),
+            v2.commit_range
+        );
+        assert_eq!(3, v2.statistics.as_ref().map_or(0, |v| v.commit_count));
+
+        let mut out = Vec::new();
+        changelog.generate(&mut out)?;
+        assert_eq!(
+            "[](v2.1.0-rc.1):sixth,v2.1.0-rc.1[](v2.0.0):fifth,v2.0.0[v2.0.0-rc.1,v2.0.0-rc.2,\
+             ](v1.0.0):second,third,fourth,v1.0.0[]():first,",
+            str::from_utf8(&out).unwrap_or_default()
+        );
+
+        Ok(())
+    }
 }
@@ -1,8 +1,10 @@
+use std::collections::{HashMap, VecDeque};
 use std::sync::LazyLock;
 
 use git_conventional::{Commit as ConventionalCommit, Footer as ConventionalFooter};
 #[cfg(feature = "repo")]
 use git2::{Commit as GitCommit, Signature as CommitSignature};
+use glob::Pattern;
 use regex::Regex;
 use serde::ser::{SerializeStruct, Serializer};
 use serde::{Deserialize, Deserializer, Serialize};
@@ -10,6 +12,7 @@ use serde_json::value::Value;
 
 use crate::config::{CommitParser, GitConfig, LinkParser, TextProcessor};
 use crate::error::{Error as AppError, Result};
+use crate::signature::{SignatureInfo, SignatureType};
 
 /// Regular expression for matching SHA1 and a following commit message
 /// separated by a whitespace.
@@ -67,6 +70,24 @@ pub struct Signature {
     pub timestamp: i64,
 }
 
+impl Signature {
+    /// Returns whether the signature matches any of the given glob patterns.
+    ///
+    /// The patterns are matched against the name, the email and the
+    /// `Name <email>` format.
+    #[must_use]
+    pub fn matches(&self, patterns: &[Pattern]) -> bool {
+        let name = self.name.as_deref().unwrap_or_default();
+        let email = self.email.as_deref().unwrap_or_default();
+        let identity = format!("{name} <{email}>");
+        patterns.iter().any(|pattern| {
+            [name, email, identity.as_str()]
+                .iter()
+                .any(|value| pattern.matches(value))
+        })
+    }
+}
+
 #[cfg(feature = "repo")]
 impl<'a> From<CommitSignature<'a>> for Signature {
     fn from(signature: CommitSignature<'a>) -> Self {
@@ -109,6 +130,85 @@ impl Range {
     }
 }
 
+/// Commit data that is exchanged with external commands.
+///
+/// Unlike the template context of [`Commit`], this always contains the full
+/// commit message and only the group and scope that are assigned to the
+/// commit, so that it can be read back without losing information.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalCommit {
+    /// Commit ID.
+    pub id: String,
+    /// Full commit message.
+    pub message: String,
+    /// Commit author.
+    #[serde(default)]
+    pub author: Signature,
+    /// Commit group assigned by a commit parser.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Commit scope assigned by a commit parser.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Arbitrary data of the commit.
+    #[serde(default)]
+    pub extra: Option<Value>,
+}
+
+impl From<&Commit<'_>> for ExternalCommit {
+    fn from(commit: &Commit<'_>) -> Self {
+        Self {
+            id: commit.id.clone(),
+            message: commit.message.clone(),
+            author: commit.author.clone(),
+            group: commit.group.clone(),
+            scope: commit.scope.clone(),
+            extra: commit.extra.clone(),
+        }
+    }
+}
+
+impl ExternalCommit {
+    /// Writes the external commits back onto the original commits.
+    ///
+    /// The commits are matched by their IDs, which allows the external command
+    /// to remove or reorder the commits. Only the message, group, scope and
+    /// extra data are taken from the external commits. Conventional commits
+    /// are parsed again if their message is changed.
+    pub fn apply<'a>(commits: Vec<Commit<'a>>, external: Vec<Self>) -> Result<Vec<Commit<'a>>> {
+        let mut originals: HashMap<String, VecDeque<Commit<'a>>> = HashMap::new();
+        for commit in commits {
+            originals
+                .entry(commit.id.clone())
+                .or_default()
+                .push_back(commit);
+        }
+        external
+            .into_iter()
+            .map(|external| {
+                let mut commit = originals
+                    .get_mut(&external.id)
+                    .and_then(VecDeque::pop_front)
+                    .ok_or_else(|| {
+                        AppError::ChangelogError(format!("Unknown commit: {}", external.id))
+                    })?;
+                if commit.message != external.message {
+                    let is_conventional = commit.conv.take().is_some();
+                    commit.message = external.message;
+                    commit.raw_message = None;
+                    if is_conventional {
+                        commit = commit.clone().into_conventional().unwrap_or(commit);
+                    }
+                }
+                commit.group = external.group;
+                commit.scope = external.scope;
+                commit.extra = external.extra;
+                Ok(commit)
+            })
+            .collect()
+    }
+}
+
 /// Common commit object that is parsed from a repository.
 #[derive(Debug, Default, Clone, PartialEq, Deserialize)]
 #[serde(rename_all(serialize = "camelCase"))]
@@ -135,9 +235,26 @@ pub struct Commit<'a> {
     pub committer: Signature,
     /// Whether if the commit has two or more parents.
     pub merge_commit: bool,
+    /// Commits that are brought in by the merge commit.
+    ///
+    /// This is only populated in the first-parent mode.
+    #[serde(default)]
+    pub merged_commits: Vec<Commit<'a>>,
     /// Per-commit diff statistics exposed to the template context.
     #[serde(default)]
     pub statistics: CommitStatistics,
+    /// Paths of the files that are changed in the commit.
+    ///
+    /// This is only populated if there are bump rules for paths.
+    #[serde(default)]
+    pub changed_files: Vec<String>,
+    /// Whether if the commit is signed.
+    #[serde(default)]
+    pub signed: bool,
+    /// Type of the commit signature.
+    pub signature_type: Option<SignatureType>,
+    /// Key that is used for signing the commit.
+    pub signer_key: Option<String>,
     /// Arbitrary data to be used with the `--from-context` CLI option.
     pub extra: Option<Value>,
     /// Remote metadata of the commit.
@@ -466,6 +583,13 @@ impl Commit<'_> {
         self
     }
 
+    /// Sets the signature information of the commit.
+    pub fn set_signature(&mut self, signature: Option<SignatureInfo>) {
+        self.signed = signature.is_some();
+        self.signature_type = signature.as_ref().map(|v| v.signature_type);
+        self.signer_key = signature.and_then(|v| v.signer_key);
+    }
+
     /// Returns an iterator over this commit's [`Footer`]s, if this is a
     /// conventional commit.
     ///
@@ -496,7 +620,7 @@ impl Serialize for Commit<'_> {
             }
         }
 
-        let mut commit = serializer.serialize_struct("Commit", 21)?;
+        let mut commit = serializer.serialize_struct("Commit", 24)?;
         commit.serialize_field("id", &self.id)?;
         if let Some(conv) = &self.conv {
             commit.serialize_field("message", conv.description())?;
@@ -530,7 +654,14 @@ impl Serialize for Commit<'_> {
         commit.serialize_field("committer", &self.committer)?;
         commit.serialize_field("conventional", &self.conv.is_some())?;
         commit.serialize_field("merge_commit", &self.merge_commit)?;
+        commit.serialize_field("merged_commits", &self.merged_commits)?;
         commit.serialize_field("statistics", &self.statistics)?;
+        if !self.changed_files.is_empty() {
+            commit.serialize_field("changed_files", &self.changed_files)?;
+        }
+        commit.serialize_field("signed", &self.signed)?;
+        commit.serialize_field("signature_type", &self.signature_type)?;
+        commit.serialize_field("signer_key", &self.signer_key)?;
         commit.serialize_field("extra", &self.extra)?;
         #[cfg(feature = "github")]
         commit.serialize_field("github", &self.github)?;
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::LazyLock;
@@ -9,9 +10,14 @@ use glob::Pattern;
 use regex::{Regex, RegexBuilder};
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
+use toml_edit::{Array, DocumentMut, Item, RawString, Table, Value};
 
+use crate::calver::{CalverFormat, DEFAULT_CALVER_FORMAT};
+use crate::commit::Commit;
 use crate::embed::EmbeddedConfig;
 use crate::error::Result;
+use crate::manifest::VersionFile;
+use crate::version::{Calver, Pep440, Semver, VersionScheme};
 use crate::{CONFIG_FILES, DEFAULT_CONFIG, command, error};
 
 /// Default initial tag.
@@ -46,6 +52,14 @@ static MANIFEST_INFO: LazyLock<Vec<ManifestInfo>> = LazyLock::new(|| {
     ]
 });
 
+/// Paths of the changelog table in the supported configuration files.
+const CHANGELOG_TABLE_PATHS: [&[&str]; 4] = [
+    &["changelog"],
+    &["package", "metadata", "git-cliff", "changelog"],
+    &["workspace", "metadata", "git-cliff", "changelog"],
+    &["tool", "git-cliff", "changelog"],
+];
+
 /// Configuration values.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -61,6 +75,12 @@ pub struct Config {
     /// Configuration values about bump version.
     #[serde(default)]
     pub bump: Bump,
+    /// External plugins for modifying the releases.
+    #[serde(default)]
+    pub plugins: Vec<Plugin>,
+    /// Packages of a monorepo.
+    #[serde(default)]
+    pub packages: Vec<Package>,
 }
 
 /// Changelog configuration.
@@ -80,6 +100,23 @@ pub struct ChangelogConfig {
     pub postprocessors: Vec<TextProcessor>,
     /// Output file path.
     pub output: Option<PathBuf>,
+    /// Regexes matching the entire versions of the yanked releases.
+    #[serde(with = "serde_whole_regex", default)]
+    pub yanked: Vec<Regex>,
+    /// Limit the number of releases included in the changelog.
+    pub limit_releases: Option<usize>,
+    /// Output file path for the releases beyond the limit.
+    pub archive_output: Option<PathBuf>,
+    /// Link to the archived releases in the template context.
+    pub archive_link: Option<String>,
+}
+
+impl ChangelogConfig {
+    /// Returns whether the given version is yanked.
+    #[must_use]
+    pub fn is_yanked(&self, version: &str) -> bool {
+        self.yanked.iter().any(|regex| regex.is_match(version))
+    }
 }
 
 /// Git configuration
@@ -118,6 +155,9 @@ pub struct GitConfig {
     pub filter_commits: bool,
     /// Fail on a commit that is not matched by any commit parser.
     pub fail_on_unmatched_commit: bool,
+    /// Require all commits to be signed.
+    #[serde(default)]
+    pub require_signed_commits: bool,
     /// Regex to select git tags that represent releases.
     #[serde(with = "serde_regex", default)]
     pub tag_pattern: Option<Regex>,
@@ -130,12 +170,33 @@ pub struct GitConfig {
     /// Regex to count matched tags.
     #[serde(with = "serde_regex", default)]
     pub count_tags: Option<Regex>,
+    /// Merge the pre-releases into the next stable release of the same
+    /// version.
+    #[serde(default)]
+    pub collapse_prereleases: bool,
     /// Include only the tags that belong to the current branch.
     pub use_branch_tags: bool,
+    /// Include only the tags that belong to the release line (e.g. `1.x`).
+    ///
+    /// If set to `branch`, the release line is detected from the name of the
+    /// current branch (e.g. `release/1.x`).
+    pub release_line: Option<String>,
     /// Order releases topologically instead of chronologically.
     pub topo_order: bool,
+    /// How to order the tags.
+    #[serde(default)]
+    pub tag_sort: TagSort,
+    /// Which date to use as the release date.
+    #[serde(default)]
+    pub release_date: ReleaseDate,
+    /// Which date of the commits to compare with `--since` and `--until`.
+    #[serde(default)]
+    pub commit_date: CommitDate,
     /// Order commits chronologically instead of topologically.
     pub topo_order_commits: bool,
+    /// Follow only the first parent of the merge commits.
+    #[serde(default)]
+    pub first_parent: bool,
     /// How to order commits in each group/release within the changelog.
     pub sort_commits: String,
     /// Limit the total number of commits included in the changelog.
@@ -148,9 +209,22 @@ pub struct GitConfig {
     /// Exclude unrelated commits with changes at the specified paths.
     #[serde(with = "serde_pattern", default)]
     pub exclude_paths: Vec<Pattern>,
+    /// Include the commits of the specified authors.
+    #[serde(with = "serde_pattern", default)]
+    pub include_authors: Vec<Pattern>,
+    /// Exclude the commits of the specified authors.
+    #[serde(with = "serde_pattern", default)]
+    pub exclude_authors: Vec<Pattern>,
+    /// Additional mailmap file for resolving the author and committer
+    /// identities.
+    pub mailmap_file: Option<PathBuf>,
 }
 
 /// Processing steps for commits.
+///
+/// Steps without arguments are written as strings (e.g. `"commit_parsers"`)
+/// while the steps with an argument are written as inline tables (e.g.
+/// `{ run_command = "jq ." }`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ProcessingStep {
@@ -167,6 +241,16 @@ pub enum ProcessingStep {
     /// An array of regex based parsers to extract links from the commit
     /// message and add them to the commit's context.
     LinkParsers,
+    /// Remove the commits with duplicate messages, keeping the first one.
+    Deduplicate,
+    /// Sort the commits by their committer date according to `sort_commits`.
+    SortCommits,
+    /// Keep only the commits with an author name or email matching any of the
+    /// glob patterns.
+    FilterByAuthor(#[serde(with = "serde_pattern")] Vec<Pattern>),
+    /// Pass the commits to an external command as JSON and read the modified
+    /// commits back from its output.
+    RunCommand(String),
 }
 
 /// Serialize and deserialize implementation for [`glob::Pattern`].
@@ -199,6 +283,81 @@ mod serde_pattern {
     }
 }
 
+/// Serialize and deserialize implementation for the regexes that match an
+/// entire string.
+mod serde_whole_regex {
+    use regex::Regex;
+    use serde::Deserialize;
+    use serde::de::Error;
+    use serde::ser::SerializeSeq;
+
+    pub fn serialize<S>(regexes: &[Regex], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(regexes.len()))?;
+        for regex in regexes {
+            let pattern = regex.as_str();
+            seq.serialize_element(
+                pattern
+                    .strip_prefix("^(?:")
+                    .and_then(|pattern| pattern.strip_suffix(")$"))
+                    .unwrap_or(pattern),
+            )?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Regex>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let patterns = Vec::<String>::deserialize(deserializer)?;
+        patterns
+            .into_iter()
+            .map(|pattern| Regex::new(&format!("^(?:{pattern})$")).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// Order of the tags.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagSort {
+    /// Order the tags by the commit time.
+    #[default]
+    Time,
+    /// Order the tags by the version via the configured versioning scheme.
+    ///
+    /// The commits of each release are the commits since the merge base with
+    /// the previous version.
+    Version,
+}
+
+/// Source of the release date.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseDate {
+    /// Use the time of the tagged commit.
+    #[default]
+    Commit,
+    /// Use the time of the annotated tag.
+    ///
+    /// The time of the tagged commit is used for lightweight tags.
+    Tag,
+}
+
+/// Date of a commit.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitDate {
+    /// Use the time when the commit was created.
+    #[default]
+    Committer,
+    /// Use the time when the change was originally authored.
+    Author,
+}
+
 /// Remote configuration.
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteConfig {
@@ -360,6 +519,83 @@ pub enum BumpType {
     Minor,
     /// Bump patch version.
     Patch,
+    /// Bump pre-release version.
+    Prerelease,
+    /// Promote the pre-release version to a normal version.
+    Release,
+}
+
+/// Level of a version increment.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum BumpLevel {
+    /// Do not increment the version.
+    None,
+    /// Increment the patch version.
+    Patch,
+    /// Increment the minor version.
+    Minor,
+    /// Increment the major version.
+    Major,
+}
+
+/// Rule for limiting the version increment of the matching commits.
+///
+/// A commit matches the rule if its scope matches the `scope` regex and all
+/// of its changed files match the `paths` patterns (if they are set).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BumpRule {
+    /// Regex for matching the commit scope.
+    #[serde(with = "serde_regex", default)]
+    pub scope: Option<Regex>,
+    /// Patterns for matching the changed files of the commit.
+    #[serde(with = "serde_pattern", default)]
+    pub paths: Vec<Pattern>,
+    /// Maximum version increment of the matching commits.
+    pub max: BumpLevel,
+}
+
+impl BumpRule {
+    /// Returns whether the given commit matches the rule.
+    #[must_use]
+    pub fn matches(&self, commit: &Commit) -> bool {
+        if self.scope.is_none() && self.paths.is_empty() {
+            return false;
+        }
+        let scope_matches = self.scope.as_ref().is_none_or(|scope| {
+            commit
+                .scope
+                .as_deref()
+                .or_else(|| {
+                    commit
+                        .conv
+                        .as_ref()
+                        .and_then(|conv| conv.scope().map(|v| v.as_str()))
+                })
+                .or(commit.default_scope.as_deref())
+                .is_some_and(|v| scope.is_match(v))
+        });
+        let paths_match = self.paths.is_empty() ||
+            (!commit.changed_files.is_empty() &&
+                commit
+                    .changed_files
+                    .iter()
+                    .all(|path| self.paths.iter().any(|pattern| pattern.matches(path))));
+        scope_matches && paths_match
+    }
+}
+
+/// Versioning scheme type.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSchemeType {
+    /// Semantic versioning.
+    #[default]
+    Semver,
+    /// Calendar versioning.
+    Calver,
+    /// PEP 440 versioning.
+    Pep440,
 }
 
 /// Bump version configuration.
@@ -389,6 +625,13 @@ pub struct Bump {
     /// When set, the version will be set to this value if no tags are found.
     pub initial_tag: Option<String>,
 
+    /// Configures whether to read the current version from the project
+    /// manifest (`Cargo.toml`, `pyproject.toml` or `package.json`).
+    ///
+    /// When enabled, the version is used as the previous version if no tags
+    /// are found.
+    pub version_from_manifest: Option<bool>,
+
     /// Configure a custom regex pattern for major version increments.
     ///
     /// This will check only the type of the commit against the given pattern.
@@ -416,8 +659,67 @@ pub struct Bump {
     /// `commit type` according to the spec is only `[a-zA-Z]+`
     pub no_increment_regex: Option<String>,
 
-    /// Force to always bump in major, minor or patch.
+    /// Force to always bump in major, minor, patch, pre-release or release.
     pub bump_type: Option<BumpType>,
+
+    /// Configures the identifier of the pre-release versions (e.g. `rc`).
+    pub prerelease_identifier: Option<String>,
+
+    /// Configures the versioning scheme.
+    #[serde(default)]
+    pub scheme: VersionSchemeType,
+
+    /// Configures the format of the calendar versions (e.g. `YYYY.0M.MICRO`).
+    ///
+    /// This is only used with the `calver` scheme.
+    pub calver_format: Option<String>,
+
+    /// Configures the rules for limiting the version increment of the
+    /// matching commits.
+    #[serde(default)]
+    pub rules: Vec<BumpRule>,
+
+    /// Configures the bump levels of the pull request labels.
+    ///
+    /// When the pull requests of the unreleased commits have any of these
+    /// labels, the highest level is used instead of the commit messages.
+    #[serde(default)]
+    pub label_rules: HashMap<String, BumpLevel>,
+
+    /// Configures the files to write the bumped version into.
+    #[serde(default)]
+    pub files: Vec<VersionFile>,
+
+    /// Configures the template for the message of the created tag.
+    ///
+    /// When not set, the changelog body template is used.
+    pub tag_message: Option<String>,
+
+    /// Configures the template for the message of the release commit.
+    ///
+    /// When not set, `chore(release): {{ version }}` is used.
+    pub commit_message: Option<String>,
+
+    /// Configures whether to sign the created tag.
+    ///
+    /// When not set, `tag.gpgSign` of the git configuration is used.
+    pub sign_tag: Option<bool>,
+
+    /// Directory that the paths of the manifests and the version files are
+    /// relative to.
+    ///
+    /// This is not configurable, it is set to the repository root (or the
+    /// path of the package) at runtime. When not set, the current directory
+    /// is used.
+    #[serde(skip)]
+    pub base_dir: Option<PathBuf>,
+
+    /// Current version that is read from the project manifest.
+    ///
+    /// This is not configurable, it is read once via
+    /// [`Bump::load_manifest_version`] after the base directory is set.
+    #[serde(skip)]
+    pub manifest_version: Option<String>,
 }
 
 impl Bump {
@@ -434,6 +736,84 @@ impl Bump {
             DEFAULT_INITIAL_TAG.into()
         }
     }
+
+    /// Reads the current version from the project manifest into
+    /// [`Bump::manifest_version`].
+    ///
+    /// The prefix of the initial tag (e.g. `v`) is prepended to the version.
+    /// The version is not set if reading the version from the manifest is not
+    /// enabled or none of the manifests contains a version.
+    pub fn load_manifest_version(&mut self) {
+        self.manifest_version = self.read_manifest_version();
+    }
+
+    /// Returns the current version from the project manifest.
+    fn read_manifest_version(&self) -> Option<String> {
+        if !self.version_from_manifest.unwrap_or(false) {
+            return None;
+        }
+        let base_dir = self.base_dir.as_deref().unwrap_or(Path::new(""));
+        let version = MANIFEST_INFO
+            .iter()
+            .map(|info| info.path.as_path())
+            .chain([Path::new("package.json")])
+            .map(|path| base_dir.join(path))
+            .filter(|path| path.exists())
+            .find_map(|path| {
+                let file = VersionFile {
+                    path: path.clone(),
+                    ..Default::default()
+                };
+                match fs::read_to_string(&path)
+                    .map_err(error::Error::from)
+                    .and_then(|contents| file.find_version(&contents))
+                {
+                    Ok(version) => version,
+                    Err(e) => {
+                        tracing::warn!("Cannot read the version from {}: {e}", path.display());
+                        None
+                    }
+                }
+            })?;
+        let prefix = self
+            .initial_tag
+            .as_deref()
+            .and_then(|tag| self.version_scheme().ok()?.parse(tag))
+            .map(|version| version.prefix)
+            .unwrap_or_default();
+        Some(format!("{prefix}{version}"))
+    }
+
+    /// Returns the version files with their paths relative to the base
+    /// directory.
+    #[must_use]
+    pub fn version_files(&self) -> Vec<VersionFile> {
+        let base_dir = self.base_dir.as_deref().unwrap_or(Path::new(""));
+        self.files
+            .iter()
+            .map(|file| VersionFile {
+                path: base_dir.join(&file.path),
+                ..file.clone()
+            })
+            .collect()
+    }
+
+    /// Returns the configured versioning scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the calendar version format is invalid.
+    pub fn version_scheme(&self) -> Result<Box<dyn VersionScheme>> {
+        Ok(match self.scheme {
+            VersionSchemeType::Semver => Box::new(Semver),
+            VersionSchemeType::Pep440 => Box::new(Pep440),
+            VersionSchemeType::Calver => Box::new(Calver::new(CalverFormat::parse(
+                self.calver_format
+                    .as_deref()
+                    .unwrap_or(DEFAULT_CALVER_FORMAT),
+            )?)),
+        })
+    }
 }
 
 /// Parser for grouping commits.
@@ -491,6 +871,72 @@ impl TextProcessor {
     }
 }
 
+/// External plugin for modifying the releases.
+///
+/// The plugin receives the list of releases as JSON via standard input and
+/// prints the modified list to the standard output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plugin {
+    /// Command to run.
+    pub command: String,
+    /// Pipeline stage to run the plugin at.
+    pub stage: PluginStage,
+}
+
+/// Pipeline stages that plugins can be run at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginStage {
+    /// After the commits are fetched from the repository.
+    AfterFetch,
+    /// After the commits and releases are processed.
+    AfterProcessing,
+    /// Right before the changelog is rendered.
+    BeforeRender,
+}
+
+impl fmt::Display for PluginStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AfterFetch => write!(f, "after_fetch"),
+            Self::AfterProcessing => write!(f, "after_processing"),
+            Self::BeforeRender => write!(f, "before_render"),
+        }
+    }
+}
+
+/// Package of a monorepo.
+///
+/// Each package gets its own changelog and version from the commits that
+/// change its paths and the tags that start with its tag prefix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Package {
+    /// Name of the package.
+    pub name: String,
+    /// Patterns for matching the paths of the package.
+    #[serde(with = "serde_pattern", default)]
+    pub paths: Vec<Pattern>,
+    /// Prefix of the tags of the package, e.g. `my-crate-v`.
+    pub tag_prefix: String,
+    /// Output file of the changelog.
+    pub output: Option<PathBuf>,
+    /// Directory of the package, relative to the repository root.
+    ///
+    /// The manifests and the version files of the package are read from
+    /// this directory.
+    pub path: Option<PathBuf>,
+    /// Files to write the bumped version of the package into.
+    ///
+    /// When not set, `bump.files` is used if the package has a path.
+    #[serde(default)]
+    pub files: Vec<VersionFile>,
+    /// Names of the packages that this package depends on.
+    ///
+    /// The package is bumped when any of its dependencies is bumped.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
 /// Parser for extracting links in commits.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkParser {
@@ -518,6 +964,152 @@ impl Config {
         Ok(None)
     }
 
+    /// Returns the path of the project manifest (e.g. Cargo.toml) that
+    /// contains the configuration.
+    pub fn retrieve_manifest_config_path() -> Result<Option<PathBuf>> {
+        for info in &(*MANIFEST_INFO) {
+            if info.path.exists() && info.regex.is_match(&fs::read_to_string(&info.path)?) {
+                return Ok(Some(info.path.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the configuration of the given package.
+    ///
+    /// The commits are filtered by the paths of the package and the tags by
+    /// its tag prefix.
+    #[must_use]
+    pub fn for_package(&self, package: &Package) -> Config {
+        let mut config = self.clone();
+        config.git.include_paths.clone_from(&package.paths);
+        config.git.tag_pattern = Some(
+            Regex::new(&format!("^{}", regex::escape(&package.tag_prefix)))
+                .expect("escaped tag prefix is a valid regex"),
+        );
+        let initial_tag = self
+            .bump
+            .initial_tag
+            .clone()
+            .unwrap_or_else(|| DEFAULT_INITIAL_TAG.to_string());
+        config.bump.initial_tag = Some(if initial_tag.starts_with(&package.tag_prefix) {
+            initial_tag
+        } else {
+            format!("{}{initial_tag}", package.tag_prefix)
+        });
+        config.changelog.output.clone_from(&package.output);
+        config.bump.base_dir.clone_from(&package.path);
+        if !package.files.is_empty() || package.path.is_none() {
+            config.bump.files.clone_from(&package.files);
+        }
+        config.packages.clear();
+        config
+    }
+
+    /// Returns the packages ordered so that the dependencies of a package
+    /// come before it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dependency is unknown or the dependencies are
+    /// circular.
+    pub fn packages_in_order(&self) -> Result<Vec<&Package>> {
+        fn visit<'a>(
+            package: &'a Package,
+            packages: &'a [Package],
+            visiting: &mut Vec<&'a str>,
+            ordered: &mut Vec<&'a Package>,
+        ) -> Result<()> {
+            if ordered.iter().any(|v| v.name == package.name) {
+                return Ok(());
+            }
+            if visiting.contains(&package.name.as_str()) {
+                return Err(error::Error::ChangelogError(format!(
+                    "Circular package dependency: {} -> {}",
+                    visiting.join(" -> "),
+                    package.name
+                )));
+            }
+            visiting.push(&package.name);
+            for dependency in &package.dependencies {
+                let dependency =
+                    packages
+                        .iter()
+                        .find(|v| &v.name == dependency)
+                        .ok_or_else(|| {
+                            error::Error::ChangelogError(format!(
+                                "Unknown dependency of package {}: {dependency}",
+                                package.name
+                            ))
+                        })?;
+                visit(dependency, packages, visiting, ordered)?;
+            }
+            visiting.pop();
+            ordered.push(package);
+            Ok(())
+        }
+        let mut ordered = Vec::with_capacity(self.packages.len());
+        for package in &self.packages {
+            visit(package, &self.packages, &mut Vec::new(), &mut ordered)?;
+        }
+        Ok(ordered)
+    }
+
+    /// Adds the given version to the yanked versions in the contents of a
+    /// TOML configuration file.
+    ///
+    /// The version is escaped so that it only matches itself.
+    ///
+    /// The formatting and the comments of the contents are preserved.
+    pub fn add_yanked_version(contents: &str, version: &str) -> Result<String> {
+        let pattern = regex::escape(version);
+        let mut document = contents.parse::<DocumentMut>().map_err(|e| {
+            error::Error::ChangelogError(format!("Cannot parse the configuration: {e}"))
+        })?;
+        let path = CHANGELOG_TABLE_PATHS.iter().find(|path| {
+            path.iter()
+                .try_fold(document.as_item(), |item, key| item.get(key))
+                .is_some_and(Item::is_table_like)
+        });
+        let changelog = match path {
+            Some(path) => path
+                .iter()
+                .try_fold(document.as_item_mut(), |item, key| item.get_mut(key))
+                .and_then(Item::as_table_like_mut),
+            None => document
+                .entry("changelog")
+                .or_insert(Item::Table(Table::new()))
+                .as_table_like_mut(),
+        }
+        .ok_or_else(|| {
+            error::Error::ChangelogError(String::from("Invalid changelog configuration"))
+        })?;
+        let yanked = changelog
+            .entry("yanked")
+            .or_insert(toml_edit::value(Array::new()))
+            .as_array_mut()
+            .ok_or_else(|| {
+                error::Error::ChangelogError(String::from("Yanked versions must be an array"))
+            })?;
+        if yanked.iter().any(|v| v.as_str() == Some(pattern.as_str())) {
+            tracing::warn!("Version is already yanked: {version}");
+            return Ok(contents.to_string());
+        }
+        // Place the new version on a new line if the array is multi-line.
+        let prefix = yanked
+            .iter()
+            .last()
+            .and_then(|v| v.decor().prefix())
+            .and_then(RawString::as_str)
+            .filter(|prefix| prefix.contains('\n'))
+            .map_or_else(
+                || String::from(if yanked.is_empty() { "" } else { " " }),
+                |prefix| format!("\n{}", prefix.rsplit('\n').next().unwrap_or_default()),
+            );
+        yanked.push_formatted(Value::from(pattern).decorated(prefix, ""));
+        Ok(document.to_string())
+    }
+
     /// Parses the config file and returns the values.
     pub fn load(path: &Path) -> Result<Config> {
         if MANIFEST_INFO
@@ -666,6 +1258,37 @@ mod test {
         assert_eq!(Duration::from_secs(30), remote1.http_timeout);
     }
 
+    #[test]
+    fn parse_processing_order() -> Result<()> {
+        let config: Config = r#"
+            [git]
+            processing_order = [
+                "commit_parsers",
+                "deduplicate",
+                "sort_commits",
+                { filter_by_author = ["Jane*"] },
+                { run_command = "cat" },
+            ]
+        "#
+        .parse()?;
+        let order = config
+            .git
+            .processing_order
+            .expect("processing order is set");
+        assert!(matches!(order[0], ProcessingStep::CommitParsers));
+        assert!(matches!(order[1], ProcessingStep::Deduplicate));
+        assert!(matches!(order[2], ProcessingStep::SortCommits));
+        assert!(matches!(
+            &order[3],
+            ProcessingStep::FilterByAuthor(patterns) if patterns[0].as_str() == "Jane*"
+        ));
+        assert!(matches!(
+            &order[4],
+            ProcessingStep::RunCommand(command) if command == "cat"
+        ));
+        Ok(())
+    }
+
     #[test]
     fn default_remote_http_timeout() -> Result<()> {
         assert_eq!(default_http_timeout(), Remote::default().http_timeout);
@@ -722,4 +1345,149 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn parse_packages() -> Result<()> {
+        let config = Config::from_str(
+            r#"
+                [bump]
+                initial_tag = "1.0.0"
+                files = [{ path = "Cargo.toml" }]
+
+                [[packages]]
+                name = "cli"
+                paths = ["crates/cli/**"]
+                tag_prefix = "cli-v"
+                output = "crates/cli/CHANGELOG.md"
+                path = "crates/cli"
+                dependencies = ["core"]
+
+                [[packages]]
+                name = "core"
+                paths = ["crates/core/**"]
+                tag_prefix = "core-v"
+            "#,
+        )?;
+        let order = config
+            .packages_in_order()?
+            .iter()
+            .map(|package| package.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["core", "cli"], order);
+
+        let cli = config.for_package(&config.packages[0]);
+        assert_eq!(vec![Pattern::new("crates/cli/**")?], cli.git.include_paths);
+        assert_eq!(
+            Some("^cli\\-v"),
+            cli.git.tag_pattern.as_ref().map(Regex::as_str)
+        );
+        assert_eq!(Some(String::from("cli-v1.0.0")), cli.bump.initial_tag);
+        assert_eq!(
+            Some(PathBuf::from("crates/cli/CHANGELOG.md")),
+            cli.changelog.output
+        );
+        assert!(cli.packages.is_empty());
+        assert_eq!(
+            vec![PathBuf::from("crates/cli/Cargo.toml")],
+            cli.bump
+                .version_files()
+                .into_iter()
+                .map(|file| file.path)
+                .collect::<Vec<_>>()
+        );
+        assert!(
+            config
+                .for_package(&config.packages[1])
+                .bump
+                .files
+                .is_empty()
+        );
+
+        let mut config = config;
+        config.packages[1].dependencies = vec![String::from("cli")];
+        assert!(config.packages_in_order().is_err());
+        config.packages[1].dependencies = vec![String::from("web")];
+        assert!(config.packages_in_order().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn yanked_versions() -> Result<()> {
+        let config = Config::from_str(&Config::add_yanked_version(
+            "[changelog]\nyanked = ['v2\\.0\\.[0-9]+']\n",
+            "v1.0.0",
+        )?)?
+        .changelog;
+        assert!(config.is_yanked("v1.0.0"));
+        assert!(config.is_yanked("v2.0.3"));
+        assert!(!config.is_yanked("v1x0y0"));
+        assert!(!config.is_yanked("v1.0.0-rc.1"));
+        assert!(!config.is_yanked("v2.1.0"));
+        assert!(Config::from_str("[changelog]\nyanked = [\"v1.(\"]\n").is_err());
+
+        for (contents, expected) in [
+            (
+                "[changelog]\nbody = \"\"\n\n[git]\n",
+                "[changelog]\nbody = \"\"\nyanked = ['v1\\.0\\.0']\n\n[git]\n",
+            ),
+            ("[git]", "[git]\n\n[changelog]\nyanked = ['v1\\.0\\.0']\n"),
+            (
+                "[changelog]\nyanked = [\"v0.1.0\"] # comment\n[git]\nyanked = []\n",
+                "[changelog]\nyanked = [\"v0.1.0\", 'v1\\.0\\.0'] # comment\n[git]\nyanked = []\n",
+            ),
+            (
+                "[changelog]\nyanked = [\n  # stable\n  \"v[0-9]\",\n]\n",
+                "[changelog]\nyanked = [\n  # stable\n  \"v[0-9]\",\n  'v1\\.0\\.0',\n]\n",
+            ),
+            (
+                "changelog = { body = \"\" } # comment\n",
+                "changelog = { body = \"\" , yanked = ['v1\\.0\\.0'] } # comment\n",
+            ),
+            (
+                "[package.metadata.git-cliff.changelog]\nyanked = ['v1\\.0\\.0']\n",
+                "[package.metadata.git-cliff.changelog]\nyanked = ['v1\\.0\\.0']\n",
+            ),
+        ] {
+            assert_eq!(expected, Config::add_yanked_version(contents, "v1.0.0")?);
+        }
+        assert!(Config::add_yanked_version("[changelog]\nyanked = 'v1'\n", "v1.0.0").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn manifest_version() -> Result<()> {
+        let dir = TempDir::new()?;
+        let mut bump = Bump {
+            version_from_manifest: Some(true),
+            initial_tag: Some(String::from("v0.1.0")),
+            base_dir: Some(dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        bump.load_manifest_version();
+        assert_eq!(None, bump.manifest_version);
+
+        fs::write(
+            dir.path().join("package.json"),
+            "{\n  \"name\": \"a\",\n  \"version\": \"0.3.0\"\n}\n",
+        )?;
+        bump.load_manifest_version();
+        assert_eq!(Some(String::from("v0.3.0")), bump.manifest_version);
+
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"a\"\nversion = \"1.2.3\"\n",
+        )?;
+        bump.load_manifest_version();
+        assert_eq!(Some(String::from("v1.2.3")), bump.manifest_version);
+        assert_eq!("v0.1.0", bump.get_initial_tag());
+
+        bump.initial_tag = None;
+        bump.load_manifest_version();
+        assert_eq!(Some(String::from("1.2.3")), bump.manifest_version);
+
+        bump.version_from_manifest = Some(false);
+        bump.load_manifest_version();
+        assert_eq!(None, bump.manifest_version);
+        Ok(())
+    }
 }
@@ -116,6 +116,13 @@ pub enum Error {
     /// [`crate::config::GitConfig::fail_on_unmatched_commit`] option is enabled.
     #[error("Found {0} unmatched commit(s)")]
     UnmatchedCommitsError(i32),
+    /// Error raised when commits are not signed and the
+    /// [`crate::config::GitConfig::require_signed_commits`] option is enabled.
+    #[error("Found {0} unsigned commit(s)")]
+    UnsignedCommitsError(i32),
+    /// Error raised when commit messages do not pass the linting checks.
+    #[error("Found {0} commit message violation(s)")]
+    LintError(usize),
 }
 
 /// Result type of the core library.
@@ -12,6 +12,8 @@
     html_favicon_url = "https://raw.githubusercontent.com/orhun/git-cliff/main/website/static/favicon/favicon.ico"
 )]
 
+/// Calendar versioning.
+pub mod calver;
 /// Changelog generator.
 pub mod changelog;
 /// Command runner.
@@ -26,6 +28,10 @@ pub mod contributor;
 pub mod embed;
 /// Error handling.
 pub mod error;
+/// Commit message linter.
+pub mod lint;
+/// Project manifests.
+pub mod manifest;
 /// Commit processing pipeline.
 pub mod process;
 /// Common release type.
@@ -37,6 +43,8 @@ pub mod remote;
 /// Git repository.
 #[cfg(feature = "repo")]
 pub mod repo;
+/// Commit and tag signatures.
+pub mod signature;
 /// Release statistics.
 pub mod statistics;
 /// Changelog commit processing summary.
@@ -45,6 +53,8 @@ pub mod summary;
 pub mod tag;
 /// Template engine.
 pub mod template;
+/// Version schemes.
+pub mod version;
 
 /// Default configuration file.
 ///
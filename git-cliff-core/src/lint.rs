@@ -0,0 +1,246 @@
+use std::fmt;
+
+use crate::commit::Commit;
+use crate::config::GitConfig;
+use crate::error::{Error as AppError, Result};
+
+/// Line that marks the start of the diff in verbose commit messages.
+///
+/// Everything after this line is discarded by `git commit`.
+const SCISSORS_LINE: &str = "# ------------------------ >8 ------------------------";
+
+/// Kind of a commit message violation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// Commit does not follow the conventional commit format.
+    ///
+    /// Reported when `require_conventional` is enabled.
+    Unconventional(String),
+    /// Commit is not matched by any of the commit parsers.
+    ///
+    /// Reported when `fail_on_unmatched_commit` is enabled.
+    Unmatched,
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unconventional(reason) => {
+                write!(f, "commit is not conventional ({reason})")
+            }
+            Self::Unmatched => write!(f, "commit was not matched by any commit parser"),
+        }
+    }
+}
+
+/// Violation found while linting a commit message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Commit ID.
+    ///
+    /// Empty if the message does not belong to an existing commit.
+    pub commit_id: String,
+    /// Commit message.
+    pub message: String,
+    /// Kind of the violation.
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.commit_id.is_empty() {
+            writeln!(f, "Commit message: {}", self.kind)?;
+        } else {
+            writeln!(
+                f,
+                "Commit {}: {}",
+                self.commit_id.chars().take(7).collect::<String>(),
+                self.kind
+            )?;
+        }
+        write!(
+            f,
+            "{}",
+            self.message
+                .lines()
+                .map(|line| format!("    | {}", line.trim()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        )
+    }
+}
+
+/// Lints the commit message against the given configuration.
+///
+/// The commit goes through the commit preprocessors and the commit parsers
+/// in the same way as in changelog generation. Commits that are skipped or
+/// filtered out by the commit parsers are not reported.
+///
+/// Violations are reported according to the `require_conventional` and
+/// `fail_on_unmatched_commit` options.
+pub fn lint_commit(commit: &Commit<'_>, config: &GitConfig) -> Result<Vec<Violation>> {
+    let commit = commit.clone().preprocess(&config.commit_preprocessors)?;
+    let (commit, conventional_error) = match commit.clone().into_conventional() {
+        Ok(commit) => (commit, None),
+        Err(AppError::ParseError(e)) => (commit, Some(e.to_string())),
+        Err(e) => return Err(e),
+    };
+    let commit_id = commit.id.clone();
+    let commit = match commit.parse(
+        &config.commit_parsers,
+        config.protect_breaking_commits,
+        config.filter_commits,
+    ) {
+        Ok(commit) => commit,
+        Err(AppError::GroupError(reason)) => {
+            tracing::trace!("{commit_id} - {reason}");
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let mut violations = Vec::new();
+    if config.require_conventional {
+        if let Some(reason) = conventional_error {
+            violations.push(Violation {
+                commit_id: commit.id.clone(),
+                message: commit.message.clone(),
+                kind: ViolationKind::Unconventional(reason),
+            });
+        }
+    }
+    if config.fail_on_unmatched_commit && commit.group.is_none() {
+        violations.push(Violation {
+            commit_id: commit.id.clone(),
+            message: commit.message.clone(),
+            kind: ViolationKind::Unmatched,
+        });
+    }
+    Ok(violations)
+}
+
+/// Lints the given commit messages.
+///
+/// See [`lint_commit`] for more information.
+pub fn lint_commits(commits: &[Commit<'_>], config: &GitConfig) -> Result<Vec<Violation>> {
+    let mut violations = Vec::new();
+    for commit in commits {
+        violations.extend(lint_commit(commit, config)?);
+    }
+    Ok(violations)
+}
+
+/// Cleans up the contents of a commit message file.
+///
+/// Removes the comment lines and everything after the scissors line, as done
+/// by `git commit` with the default cleanup mode.
+#[must_use]
+pub fn clean_message(contents: &str) -> String {
+    contents
+        .lines()
+        .take_while(|line| *line != SCISSORS_LINE)
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use regex::Regex;
+
+    use super::*;
+    use crate::config::CommitParser;
+
+    fn lint_config() -> GitConfig {
+        GitConfig {
+            conventional_commits: true,
+            require_conventional: true,
+            fail_on_unmatched_commit: true,
+            commit_parsers: vec![
+                CommitParser {
+                    message: Regex::new("^feat").ok(),
+                    group: Some(String::from("Features")),
+                    ..Default::default()
+                },
+                CommitParser {
+                    message: Regex::new("^chore\\(release\\)").ok(),
+                    skip: Some(true),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn lint_valid_commit() -> Result<()> {
+        let commit = Commit::new(String::from("123456789"), String::from("feat: add lint"));
+        assert_eq!(
+            Vec::<Violation>::new(),
+            lint_commit(&commit, &lint_config())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lint_skipped_commit() -> Result<()> {
+        let commit = Commit::new(
+            String::from("123456789"),
+            String::from("chore(release): prepare for v1.0.0"),
+        );
+        assert_eq!(
+            Vec::<Violation>::new(),
+            lint_commit(&commit, &lint_config())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lint_invalid_commits() -> Result<()> {
+        let commits = vec![
+            Commit::new(String::from("123456789"), String::from("fix: typo")),
+            Commit::new(String::from("abcdefghi"), String::from("added stuff")),
+        ];
+        let violations = lint_commits(&commits, &lint_config())?;
+        assert_eq!(
+            vec![
+                ViolationKind::Unmatched,
+                ViolationKind::Unconventional(String::from(
+                    "Missing type in the commit summary, expected `type: description`"
+                )),
+                ViolationKind::Unmatched,
+            ],
+            violations
+                .iter()
+                .map(|v| v.kind.clone())
+                .collect::<Vec<ViolationKind>>()
+        );
+        assert_eq!(
+            "Commit abcdefg: commit was not matched by any commit parser\n    | added stuff",
+            violations[2].to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lint_without_checks() -> Result<()> {
+        let config = GitConfig {
+            require_conventional: false,
+            fail_on_unmatched_commit: false,
+            ..lint_config()
+        };
+        let commit = Commit::new(String::new(), String::from("added stuff"));
+        assert_eq!(Vec::<Violation>::new(), lint_commit(&commit, &config)?);
+        Ok(())
+    }
+
+    #[test]
+    fn clean_commit_message() {
+        let contents = "feat: add lint\n\nBody\n# Please enter the commit message\n#\n# \
+                        ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+        assert_eq!("feat: add lint\n\nBody", clean_message(contents));
+    }
+}
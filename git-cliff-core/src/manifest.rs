@@ -0,0 +1,359 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// Placeholder of the version in the custom patterns.
+const VERSION_PLACEHOLDER: &str = "{version}";
+
+/// Regex for matching the version of a `Cargo.toml` file.
+///
+/// The version is taken from the `[package]` or `[workspace.package]` table.
+static CARGO_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^\[(?:workspace\.)?package\][^\n]*(?:\n(?:[^\[\n][^\n]*)?)*?\n[ \t]*version[ \t]*=[ \t]*"(?P<version>[^"]*)""#,
+    )
+    .expect("valid Cargo.toml regex")
+});
+
+/// Regex for matching the version of a `package.json` file.
+static NPM_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#""version"\s*:\s*"(?P<version>[^"]*)""#).expect("valid package.json regex")
+});
+
+/// Regex for matching the version of a `pyproject.toml` file.
+///
+/// The version is taken from the `[project]` or `[tool.poetry]` table.
+static PYPROJECT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^\[(?:project|tool\.poetry)\][^\n]*(?:\n(?:[^\[\n][^\n]*)?)*?\n[ \t]*version[ \t]*=[ \t]*["'](?P<version>[^"']*)["']"#,
+    )
+    .expect("valid pyproject.toml regex")
+});
+
+/// Builtin format of a version file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionFileFormat {
+    /// `Cargo.toml` of a Rust package or workspace.
+    Cargo,
+    /// `package.json` of a Node.js package.
+    Npm,
+    /// `pyproject.toml` of a Python project.
+    Pyproject,
+}
+
+impl VersionFileFormat {
+    /// Detects the format from the name of the given file.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.toml" => Some(Self::Cargo),
+            "package.json" => Some(Self::Npm),
+            "pyproject.toml" => Some(Self::Pyproject),
+            _ => None,
+        }
+    }
+
+    /// Returns the regex for matching the version.
+    fn regex(self) -> &'static Regex {
+        match self {
+            Self::Cargo => &CARGO_REGEX,
+            Self::Npm => &NPM_REGEX,
+            Self::Pyproject => &PYPROJECT_REGEX,
+        }
+    }
+}
+
+/// File that contains the version of the project.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct VersionFile {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Builtin format of the file.
+    ///
+    /// If neither the format nor the pattern is set, it is detected from the
+    /// name of the file.
+    pub format: Option<VersionFileFormat>,
+    /// Regex for matching the version, e.g. `VERSION = "{version}"`.
+    ///
+    /// The `{version}` placeholder is replaced with the version.
+    pub pattern: Option<String>,
+}
+
+impl VersionFile {
+    /// Returns the regex for matching the version with the `version` group.
+    fn regex(&self) -> Result<Regex> {
+        if let Some(pattern) = &self.pattern {
+            if !pattern.contains(VERSION_PLACEHOLDER) {
+                return Err(Error::ChangelogError(format!(
+                    "Version file pattern does not contain {VERSION_PLACEHOLDER}: {pattern}"
+                )));
+            }
+            return Ok(Regex::new(&pattern.replacen(
+                VERSION_PLACEHOLDER,
+                r"(?P<version>\d[0-9A-Za-z.+\-]*)",
+                1,
+            ))?);
+        }
+        self.format
+            .or_else(|| VersionFileFormat::from_path(&self.path))
+            .map(|format| format.regex().clone())
+            .ok_or_else(|| {
+                Error::ChangelogError(format!(
+                    "Cannot detect the format of the version file: {}",
+                    self.path.display()
+                ))
+            })
+    }
+
+    /// Returns the first version in the given contents of the file.
+    pub fn find_version(&self, contents: &str) -> Result<Option<String>> {
+        Ok(self
+            .regex()?
+            .captures(contents)
+            .and_then(|captures| captures.name("version"))
+            .map(|version| version.as_str().to_string()))
+    }
+
+    /// Replaces the version in the given contents of the file.
+    ///
+    /// All the matches are replaced for custom patterns, whereas only the
+    /// first one is replaced for the builtin formats.
+    pub fn update(&self, contents: &str, version: &str) -> Result<String> {
+        let regex = self.regex()?;
+        let matches = regex
+            .captures_iter(contents)
+            .filter_map(|captures| captures.name("version"));
+        let matches = if self.pattern.is_some() {
+            matches.collect::<Vec<_>>()
+        } else {
+            matches.take(1).collect()
+        };
+        let mut updated = String::with_capacity(contents.len());
+        let mut last_end = 0;
+        for m in &matches {
+            updated.push_str(&contents[last_end..m.start()]);
+            updated.push_str(version);
+            last_end = m.end();
+        }
+        if matches.is_empty() {
+            return Err(Error::ChangelogError(format!(
+                "Cannot find the version in {}",
+                self.path.display()
+            )));
+        }
+        updated.push_str(&contents[last_end..]);
+        Ok(updated)
+    }
+}
+
+/// Writes the given version into the version files.
+///
+/// All the files are updated in memory before any of them is written, and
+/// each file is written to a temporary file first which is then renamed. So
+/// the files are left untouched if the version cannot be found in one of them.
+pub fn write_version(files: &[VersionFile], version: &str) -> Result<()> {
+    if files.is_empty() {
+        tracing::warn!("No version files are configured, there is nothing to write");
+        return Ok(());
+    }
+    let updates = files
+        .iter()
+        .map(|file| {
+            let contents = fs::read_to_string(&file.path)?;
+            Ok((file, file.update(&contents, version)?))
+        })
+        .collect::<Result<Vec<(&VersionFile, String)>>>()?;
+    let mut renames = Vec::with_capacity(updates.len());
+    for (file, contents) in updates {
+        let mut temp_name = file.path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = file.path.with_file_name(temp_name);
+        fs::write(&temp_path, contents)?;
+        renames.push((temp_path, &file.path));
+    }
+    for (temp_path, path) in renames {
+        tracing::info!("Writing version {version} to {}", path.display());
+        fs::rename(temp_path, path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn update_builtin_formats() -> Result<()> {
+        let cargo = VersionFile {
+            path: PathBuf::from("Cargo.toml"),
+            ..Default::default()
+        };
+        assert_eq!(
+            r#"[workspace]
+members = ["a"]
+
+[workspace.package]
+edition = "2024"
+version = "1.3.0"
+
+[dependencies]
+version = "0.1.0"
+"#,
+            cargo.update(
+                r#"[workspace]
+members = ["a"]
+
+[workspace.package]
+edition = "2024"
+version = "1.2.0"
+
+[dependencies]
+version = "0.1.0"
+"#,
+                "1.3.0"
+            )?
+        );
+        assert!(
+            cargo
+                .update(
+                    "[package]\nname = \"a\"\nversion.workspace = true\n",
+                    "1.3.0"
+                )
+                .is_err()
+        );
+
+        let npm = VersionFile {
+            path: PathBuf::from("web/package.json"),
+            ..Default::default()
+        };
+        assert_eq!(
+            "{\n  \"name\": \"a\",\n  \"version\": \"2.0.0\"\n}",
+            npm.update(
+                "{\n  \"name\": \"a\",\n  \"version\": \"1.0.0\"\n}",
+                "2.0.0"
+            )?
+        );
+
+        let pyproject = VersionFile {
+            path: PathBuf::from("pyproject.toml"),
+            format: Some(VersionFileFormat::Pyproject),
+            ..Default::default()
+        };
+        assert_eq!(
+            "[tool.black]\nversion = 'x'\n\n[project]\nname = \"a\"\nversion = \"0.2.0\"\n",
+            pyproject.update(
+                "[tool.black]\nversion = 'x'\n\n[project]\nname = \"a\"\nversion = \"0.1.0\"\n",
+                "0.2.0"
+            )?
+        );
+
+        assert!(
+            VersionFile {
+                path: PathBuf::from("version.txt"),
+                ..Default::default()
+            }
+            .update("1.0.0", "2.0.0")
+            .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn update_with_pattern() -> Result<()> {
+        let file = VersionFile {
+            path: PathBuf::from("src/version.py"),
+            pattern: Some(String::from(r#"__version__ = "{version}""#)),
+            ..Default::default()
+        };
+        assert_eq!(
+            "__version__ = \"1.1.0\"\n# __version__ = \"1.1.0\"\n",
+            file.update(
+                "__version__ = \"1.0.0\"\n# __version__ = \"1.0.0\"\n",
+                "1.1.0"
+            )?
+        );
+        assert!(file.update("VERSION = 1", "1.1.0").is_err());
+        assert!(
+            VersionFile {
+                pattern: Some(String::from("VERSION")),
+                ..file
+            }
+            .update("VERSION", "1.1.0")
+            .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_version() -> Result<()> {
+        let cargo = VersionFile {
+            path: PathBuf::from("Cargo.toml"),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(String::from("0.7.0")),
+            cargo.find_version(
+                "[package]\nname = \"a\"\nversion = \"0.7.0\"\n\n[dependencies]\nversion = \
+                 \"1.0.0\"\n"
+            )?
+        );
+        assert_eq!(
+            None,
+            cargo.find_version("[workspace]\nmembers = [\"a\"]\n")?
+        );
+
+        let npm = VersionFile {
+            path: PathBuf::from("package.json"),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(String::from("2.1.0")),
+            npm.find_version("{\n  \"name\": \"a\",\n  \"version\": \"2.1.0\"\n}")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_version_to_files() -> Result<()> {
+        let temp_dir = TempDir::with_prefix("git-cliff-")?;
+        let cargo = temp_dir.path().join("Cargo.toml");
+        let version = temp_dir.path().join("VERSION");
+        fs::write(&cargo, "[package]\nversion = \"0.1.0\"\n")?;
+        fs::write(&version, "0.1.0\n")?;
+        let files = [
+            VersionFile {
+                path: cargo.clone(),
+                ..Default::default()
+            },
+            VersionFile {
+                path: version.clone(),
+                pattern: Some(String::from("^{version}")),
+                ..Default::default()
+            },
+        ];
+
+        write_version(&files, "0.2.0")?;
+        assert_eq!(
+            "[package]\nversion = \"0.2.0\"\n",
+            fs::read_to_string(&cargo)?
+        );
+        assert_eq!("0.2.0\n", fs::read_to_string(&version)?);
+
+        fs::write(&version, "unknown\n")?;
+        assert!(write_version(&files, "0.3.0").is_err());
+        assert_eq!(
+            "[package]\nversion = \"0.2.0\"\n",
+            fs::read_to_string(&cargo)?
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
-use crate::commit::Commit;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use glob::Pattern;
+
+use crate::command;
+use crate::commit::{Commit, ExternalCommit};
 use crate::config::{GitConfig, ProcessingStep};
 use crate::error::{Error as AppError, Result};
 use crate::summary::Summary;
@@ -19,7 +25,7 @@ impl<'cfg, 'sum> CommitProcessor<'cfg, 'sum> {
     /// Runs commit processing and final validation checks.
     pub fn run<'a>(&mut self, commits: &mut Vec<Commit<'a>>) -> Result<()> {
         if let Some(order) = &self.config.processing_order {
-            self.run_with_order(commits, order);
+            self.run_with_order(commits, order)?;
         } else {
             self.run_legacy(commits);
         }
@@ -30,12 +36,19 @@ impl<'cfg, 'sum> CommitProcessor<'cfg, 'sum> {
         if self.config.fail_on_unmatched_commit {
             self.check_unmatched_commits(commits)?;
         }
+        if self.config.require_signed_commits {
+            self.check_signed_commits(commits)?;
+        }
 
         Ok(())
     }
 
     /// Applies commit processing steps in the configured linear order.
-    fn run_with_order<'a>(&mut self, commits: &mut Vec<Commit<'a>>, order: &[ProcessingStep]) {
+    fn run_with_order<'a>(
+        &mut self,
+        commits: &mut Vec<Commit<'a>>,
+        order: &[ProcessingStep],
+    ) -> Result<()> {
         for step in order {
             match step {
                 ProcessingStep::CommitPreprocessors => self.apply_commit_preprocessors(commits),
@@ -43,8 +56,15 @@ impl<'cfg, 'sum> CommitProcessor<'cfg, 'sum> {
                 ProcessingStep::ConventionalCommits => self.apply_conventional_commits(commits),
                 ProcessingStep::CommitParsers => self.apply_commit_parsers(commits),
                 ProcessingStep::LinkParsers => self.apply_link_parsers(commits),
+                ProcessingStep::Deduplicate => self.apply_deduplicate(commits),
+                ProcessingStep::SortCommits => self.apply_sort_commits(commits),
+                ProcessingStep::FilterByAuthor(patterns) => {
+                    self.apply_filter_by_author(commits, patterns)
+                }
+                ProcessingStep::RunCommand(command) => self.apply_run_command(commits, command)?,
             }
         }
+        Ok(())
     }
 
     /// Preserves the historical non-linear processing flow for compatibility.
@@ -181,6 +201,65 @@ impl<'cfg, 'sum> CommitProcessor<'cfg, 'sum> {
         *commits = processed;
     }
 
+    /// Removes the commits with duplicate messages, keeping the first one.
+    fn apply_deduplicate<'a>(&mut self, commits: &mut Vec<Commit<'a>>) {
+        let mut messages = HashSet::new();
+        commits.retain(|commit| {
+            let is_unique = messages.insert(commit.message.trim().to_string());
+            if !is_unique {
+                self.on_processing_error(
+                    commit,
+                    &AppError::GroupError(String::from("Skipping duplicate commit")),
+                );
+            }
+            is_unique
+        });
+    }
+
+    /// Sorts the commits by their committer date according to `sort_commits`.
+    fn apply_sort_commits<'a>(&mut self, commits: &mut [Commit<'a>]) {
+        if self.config.sort_commits.eq_ignore_ascii_case("newest") {
+            commits.sort_by_key(|commit| Reverse(commit.committer.timestamp));
+        } else {
+            commits.sort_by_key(|commit| commit.committer.timestamp);
+        }
+    }
+
+    /// Keeps only the commits with an author matching the given glob patterns.
+    ///
+    /// The patterns are matched against the author name, email and
+    /// `Name <email>` format.
+    fn apply_filter_by_author<'a>(&mut self, commits: &mut Vec<Commit<'a>>, patterns: &[Pattern]) {
+        commits.retain(|commit| {
+            let is_match = commit.author.matches(patterns);
+            if !is_match {
+                self.on_processing_error(
+                    commit,
+                    &AppError::GroupError(String::from("Skipping commit of unmatched author")),
+                );
+            }
+            is_match
+        });
+    }
+
+    /// Passes the commits to an external command and reads them back.
+    ///
+    /// The commits are written to the standard input of the command as a JSON
+    /// array of [`ExternalCommit`]s. The command is expected to print the
+    /// modified array to the standard output. Only the editable fields of the
+    /// commits are read back; see [`ExternalCommit::apply`].
+    fn apply_run_command<'a>(
+        &mut self,
+        commits: &mut Vec<Commit<'a>>,
+        command: &str,
+    ) -> Result<()> {
+        let external = commits.iter().map(ExternalCommit::from).collect::<Vec<_>>();
+        let output = command::run(command, Some(serde_json::to_string(&external)?), vec![])?;
+        let external: Vec<ExternalCommit> = serde_json::from_str(&output)?;
+        *commits = ExternalCommit::apply(std::mem::take(commits), external)?;
+        Ok(())
+    }
+
     /// Processes one commit with the legacy single-pass pipeline.
     fn process_single_commit<'a>(&mut self, commit: &Commit<'a>) -> Option<Commit<'a>> {
         match commit.process(self.config) {
@@ -249,6 +328,32 @@ impl<'cfg, 'sum> CommitProcessor<'cfg, 'sum> {
         Ok(())
     }
 
+    /// Validates that all processed commits are signed.
+    fn check_signed_commits(&self, commits: &[Commit<'_>]) -> Result<()> {
+        tracing::debug!("Verifying that all commits are signed");
+        let mut unsigned_count = 0;
+        commits.iter().for_each(|commit| {
+            if !commit.signed {
+                tracing::error!(
+                    "Commit {id} is not signed:\n{message}",
+                    id = commit.id.chars().take(7).collect::<String>(),
+                    message = commit
+                        .message
+                        .lines()
+                        .map(|line| { format!("    | {}", line.trim()) })
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                );
+                unsigned_count += 1;
+            }
+        });
+
+        if unsigned_count > 0 {
+            return Err(AppError::UnsignedCommitsError(unsigned_count));
+        }
+        Ok(())
+    }
+
     /// Emits a trace log entry for a commit-processing failure.
     fn on_processing_error(&self, commit: &Commit<'_>, error: &AppError) {
         let short_id = commit.id.chars().take(7).collect::<String>();
@@ -263,6 +368,7 @@ mod test {
 
     use super::*;
     use crate::config::{CommitParser, ProcessingStep};
+    use crate::signature::{SignatureInfo, SignatureType};
 
     #[test]
     fn list_keeps_legacy_behavior_when_order_is_unset() -> Result<()> {
@@ -363,4 +469,115 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn require_signed_commits() -> Result<()> {
+        let mut commits = vec![
+            Commit::new(String::from("1234567"), String::from("fix: typo")),
+            Commit::new(String::from("abcdefg"), String::from("feat: add xyz")),
+        ];
+        commits[0].set_signature(Some(SignatureInfo {
+            signature_type: SignatureType::Gpg,
+            signer_key: None,
+        }));
+        let cfg = crate::config::GitConfig {
+            require_signed_commits: true,
+            ..Default::default()
+        };
+
+        let result = CommitProcessor::new(&cfg, &mut Summary::default()).run(&mut commits);
+        assert!(matches!(result, Err(AppError::UnsignedCommitsError(1))));
+
+        commits.truncate(1);
+        CommitProcessor::new(&cfg, &mut Summary::default()).run(&mut commits)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn ordered_deduplicate_and_sort_commits() -> Result<()> {
+        let mut commits = vec![
+            Commit::new(String::from("1"), String::from("fix: typo")),
+            Commit::new(String::from("2"), String::from("feat: add xyz")),
+            Commit::new(String::from("3"), String::from("fix: typo")),
+        ];
+        for (i, commit) in commits.iter_mut().enumerate() {
+            commit.committer.timestamp = i as i64;
+        }
+        let cfg = crate::config::GitConfig {
+            processing_order: Some(vec![
+                ProcessingStep::Deduplicate,
+                ProcessingStep::SortCommits,
+            ]),
+            sort_commits: String::from("newest"),
+            ..Default::default()
+        };
+
+        CommitProcessor::new(&cfg, &mut Summary::default()).run(&mut commits)?;
+        assert_eq!(
+            vec!["2", "1"],
+            commits.iter().map(|c| c.id.as_str()).collect::<Vec<&str>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ordered_filter_by_author() -> Result<()> {
+        let mut commits = vec![
+            Commit::new(String::from("1"), String::from("fix: typo")),
+            Commit::new(String::from("2"), String::from("feat: add xyz")),
+        ];
+        commits[0].author.name = Some(String::from("Jane Doe"));
+        commits[1].author.email = Some(String::from("bot@example.com"));
+        let cfg = crate::config::GitConfig {
+            processing_order: Some(vec![ProcessingStep::FilterByAuthor(vec![Pattern::new(
+                "Jane*",
+            )?])]),
+            ..Default::default()
+        };
+
+        CommitProcessor::new(&cfg, &mut Summary::default()).run(&mut commits)?;
+        assert_eq!(1, commits.len());
+        assert_eq!("1", commits[0].id);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn ordered_run_command() -> Result<()> {
+        let mut commits = vec![
+            Commit::new(String::from("1"), String::from("feat(core): add xyz")),
+            Commit::new(String::from("2"), String::from("update xyz")),
+            Commit::new(String::from("3"), String::from("remove xyz")),
+        ];
+        commits[1].default_scope = Some(String::from("misc"));
+        let cfg = crate::config::GitConfig {
+            processing_order: Some(vec![
+                ProcessingStep::ConventionalCommits,
+                ProcessingStep::RunCommand(String::from(
+                    r#"sed -e 's/xyz/abc/g' -e 's/,{"id":"3"[^}]*}[^}]*}//'"#,
+                )),
+            ]),
+            conventional_commits: true,
+            ..Default::default()
+        };
+
+        CommitProcessor::new(&cfg, &mut Summary::default()).run(&mut commits)?;
+        assert_eq!(
+            vec!["feat(core): add abc", "update abc"],
+            commits
+                .iter()
+                .map(|c| c.message.as_str())
+                .collect::<Vec<&str>>()
+        );
+        assert!(commits[0].conv.is_some());
+        assert_eq!(None, commits[0].group);
+        assert_eq!(None, commits[0].scope);
+        assert!(commits[1].conv.is_none());
+        assert_eq!(Some(String::from("misc")), commits[1].default_scope);
+
+        Ok(())
+    }
 }
@@ -1,13 +1,12 @@
 use std::collections::HashMap;
 
-use next_version::{NextVersion as NextVersionTrait, VersionUpdater};
-use semver::Version;
 use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
 
-use crate::commit::{Commit, Range, commits_to_conventional_commits};
+use crate::commit::{Commit, ExternalCommit, Range, Signature, commits_to_conventional_commits};
 use crate::config::{Bump, BumpType};
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::signature::SignatureType;
 use crate::statistics::Statistics;
 #[cfg(feature = "remote")]
 use crate::{
@@ -50,6 +49,31 @@ pub struct Release<'a> {
     /// The type of version bump that was applied.
     #[serde(rename = "bump_type")]
     pub bump_type: Option<BumpType>,
+    /// Whether if the tag of the release is signed.
+    #[serde(default)]
+    pub signed: bool,
+    /// Type of the tag signature.
+    #[serde(rename = "signature_type")]
+    pub signature_type: Option<SignatureType>,
+    /// Key that is used for signing the tag.
+    #[serde(rename = "signer_key")]
+    pub signer_key: Option<String>,
+    /// Whether if the release is yanked.
+    #[serde(default)]
+    pub yanked: bool,
+    /// Tagger of the release tag (only if it was annotated).
+    pub tagger: Option<Signature>,
+    /// Time of the release tag (only if it was annotated).
+    #[serde(rename = "tag_timestamp")]
+    pub tag_timestamp: Option<i64>,
+    /// Updated dependencies that require a new release.
+    ///
+    /// This is only populated for the unreleased changes of the packages.
+    #[serde(default, rename = "dependency_updates")]
+    pub dependency_updates: Vec<DependencyUpdate>,
+    /// Pre-releases that are collapsed into the release.
+    #[serde(default)]
+    pub prereleases: Vec<Prerelease>,
     /// Contributors.
     #[cfg(feature = "github")]
     pub github: RemoteReleaseMetadata,
@@ -68,6 +92,99 @@ pub struct Release<'a> {
     pub azure_devops: RemoteReleaseMetadata,
 }
 
+/// Release data that is exchanged with external commands.
+///
+/// See [`ExternalCommit`] for the commits of the release.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalRelease {
+    /// Release version, git tag.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// git tag's message.
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Timestamp of the release in seconds, from epoch.
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// Commits made for the release.
+    #[serde(default)]
+    pub commits: Vec<ExternalCommit>,
+    /// Arbitrary data of the release.
+    #[serde(default)]
+    pub extra: Option<Value>,
+}
+
+impl From<&Release<'_>> for ExternalRelease {
+    fn from(release: &Release<'_>) -> Self {
+        Self {
+            version: release.version.clone(),
+            message: release.message.clone(),
+            timestamp: release.timestamp,
+            commits: release.commits.iter().map(ExternalCommit::from).collect(),
+            extra: release.extra.clone(),
+        }
+    }
+}
+
+impl ExternalRelease {
+    /// Writes the external releases back onto the original releases.
+    ///
+    /// The releases are matched by their position, thus the external command
+    /// cannot add or remove releases. Only the commits and the extra data are
+    /// taken from the external releases.
+    pub fn apply<'a>(releases: Vec<Release<'a>>, external: Vec<Self>) -> Result<Vec<Release<'a>>> {
+        if releases.len() != external.len() {
+            return Err(Error::ChangelogError(format!(
+                "Expected {} releases, got {}",
+                releases.len(),
+                external.len()
+            )));
+        }
+        releases
+            .into_iter()
+            .zip(external)
+            .map(|(mut release, external)| {
+                release.commits = ExternalCommit::apply(release.commits, external.commits)?;
+                release.extra = external.extra;
+                Ok(release)
+            })
+            .collect()
+    }
+}
+
+/// Dependency of a package that is updated to a new version.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyUpdate {
+    /// Name of the dependency.
+    pub name: String,
+    /// Next version of the dependency.
+    pub version: String,
+}
+
+/// Pre-release that is collapsed into a release.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Prerelease {
+    /// Pre-release version, git tag.
+    pub version: Option<String>,
+    /// git tag's message.
+    pub message: Option<String>,
+    /// Commit ID of the tag.
+    pub commit_id: Option<String>,
+    /// Timestamp of the pre-release in seconds, from epoch.
+    pub timestamp: Option<i64>,
+}
+
+impl From<&Release<'_>> for Prerelease {
+    fn from(release: &Release<'_>) -> Self {
+        Self {
+            version: release.version.clone(),
+            message: release.message.clone(),
+            commit_id: release.commit_id.clone(),
+            timestamp: release.timestamp,
+        }
+    }
+}
+
 #[cfg(feature = "github")]
 crate::update_release_metadata!(github, update_github_metadata);
 
@@ -132,110 +249,43 @@ impl Release<'_> {
         crate::set_progress_message!(
             "Calculating the next version from commits with custom bump rules"
         );
-        match self
-            .previous
-            .as_ref()
-            .and_then(|release| release.version.clone())
-        {
-            Some(version) => {
-                let mut semver = Version::parse(&version);
-                let mut prefix = None;
-                if semver.is_err() && version.split('.').count() >= 2 {
-                    let mut found_numeric = false;
-                    for (i, c) in version.char_indices() {
-                        if c.is_numeric() && !found_numeric {
-                            found_numeric = true;
-                            let version_prefix = version[..i].to_string();
-                            let remaining = version[i..].to_string();
-                            let version = Version::parse(&remaining);
-                            if version.is_ok() {
-                                semver = version;
-                                prefix = Some(version_prefix);
-                                break;
-                            }
-                        } else if !c.is_numeric() && found_numeric {
-                            found_numeric = false;
-                        }
-                    }
-                }
-                let mut next_version = VersionUpdater::new()
-                    .with_features_always_increment_minor(
-                        config.features_always_bump_minor.unwrap_or(true),
-                    )
-                    .with_breaking_always_increment_major(
-                        config.breaking_always_bump_major.unwrap_or(true),
-                    );
-                if let Some(custom_major_increment_regex) = &config.custom_major_increment_regex {
-                    next_version = next_version
-                        .with_custom_major_increment_regex(custom_major_increment_regex)?;
-                }
-                if let Some(custom_minor_increment_regex) = &config.custom_minor_increment_regex {
-                    next_version = next_version
-                        .with_custom_minor_increment_regex(custom_minor_increment_regex)?;
-                }
-                if let Some(no_increment_regex) = &config.no_increment_regex {
-                    next_version = next_version.with_no_increment_regex(no_increment_regex)?;
-                }
-                let old_semver = semver?;
-                let (next_version, determined_bump_type) =
-                    if let Some(bump_type) = &config.bump_type {
-                        let v = match bump_type {
-                            BumpType::Major => old_semver.increment_major().to_string(),
-                            BumpType::Minor => old_semver.increment_minor().to_string(),
-                            BumpType::Patch => old_semver.increment_patch().to_string(),
-                        };
-                        (v, Some(*bump_type))
-                    } else {
-                        let new_semver = next_version.increment(
-                            &old_semver,
-                            self.commits
-                                .iter()
-                                .map(|commit| commit.raw_message().trim_end().to_string())
-                                .collect::<Vec<String>>(),
-                        );
-                        let bump_type = determine_bump_type(&old_semver, &new_semver);
-                        (new_semver.to_string(), bump_type)
-                    };
-                let version = if let Some(prefix) = prefix {
-                    format!("{prefix}{next_version}")
-                } else {
-                    next_version
-                };
-                Ok(NextVersion {
-                    version,
-                    bump_type: determined_bump_type,
-                })
-            }
-            None => Ok(NextVersion {
-                version: config.get_initial_tag(),
-                bump_type: None,
-            }),
-        }
+        config.version_scheme()?.bump(self, config)
     }
 }
 
 /// Representation of a calculated next version.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct NextVersion {
+    /// Previous version string.
+    pub previous_version: Option<String>,
     /// Version string.
     pub version: String,
     /// Type of version bump that was applied.
     pub bump_type: Option<BumpType>,
+    /// Commits that triggered the highest bump.
+    ///
+    /// This is empty if the version is not calculated from the commits.
+    pub commits: Vec<BumpReason>,
 }
 
-/// Determines the bump type by comparing two semver versions.
-fn determine_bump_type(old: &Version, new: &Version) -> Option<BumpType> {
-    if new.major != old.major {
-        Some(BumpType::Major)
-    } else if new.minor != old.minor {
-        Some(BumpType::Minor)
-    } else if new.patch != old.patch {
-        Some(BumpType::Patch)
-    } else {
-        None
+impl NextVersion {
+    /// Returns the next version as JSON.
+    pub fn as_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
     }
 }
 
+/// Commit that triggered a version bump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BumpReason {
+    /// Commit ID.
+    pub id: String,
+    /// First line of the commit message.
+    pub message: String,
+    /// Rule that decided the bump, e.g. `feature` or `label: semver:major`.
+    pub rule: String,
+}
+
 /// Representation of a list of releases.
 #[derive(Serialize)]
 pub struct Releases<'a> {
@@ -255,6 +305,7 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::config::VersionSchemeType;
     #[test]
     fn bump_version() -> Result<()> {
         fn build_release<'a>(version: &str, commits: &'a [&str]) -> Release<'a> {
@@ -277,6 +328,14 @@ mod test {
                 submodule_commits: HashMap::new(),
                 statistics: None,
                 bump_type: None,
+                signed: false,
+                signature_type: None,
+                signer_key: None,
+                yanked: false,
+                tagger: None,
+                tag_timestamp: None,
+                dependency_updates: Vec::new(),
+                prereleases: Vec::new(),
                 #[cfg(feature = "github")]
                 github: crate::remote::RemoteReleaseMetadata {
                     contributors: vec![],
@@ -372,10 +431,22 @@ mod test {
                     features_always_bump_minor: Some(false),
                     breaking_always_bump_major: Some(false),
                     initial_tag: None,
+                    version_from_manifest: None,
                     custom_major_increment_regex: None,
                     custom_minor_increment_regex: None,
                     no_increment_regex: None,
                     bump_type: None,
+                    prerelease_identifier: None,
+                    scheme: VersionSchemeType::Semver,
+                    calver_format: None,
+                    rules: vec![],
+                    label_rules: HashMap::new(),
+                    files: vec![],
+                    tag_message: None,
+                    commit_message: None,
+                    sign_tag: None,
+                    base_dir: None,
+                    manifest_version: None,
                 })?
                 .version;
             assert_eq!(expected_version, &next_version);
@@ -398,10 +469,22 @@ mod test {
                     features_always_bump_minor: Some(true),
                     breaking_always_bump_major: Some(false),
                     initial_tag: None,
+                    version_from_manifest: None,
                     custom_major_increment_regex: None,
                     custom_minor_increment_regex: None,
                     no_increment_regex: None,
                     bump_type: None,
+                    prerelease_identifier: None,
+                    scheme: VersionSchemeType::Semver,
+                    calver_format: None,
+                    rules: vec![],
+                    label_rules: HashMap::new(),
+                    files: vec![],
+                    tag_message: None,
+                    commit_message: None,
+                    sign_tag: None,
+                    base_dir: None,
+                    manifest_version: None,
                 })?
                 .version;
             assert_eq!(expected_version, &next_version);
@@ -424,10 +507,22 @@ mod test {
                     features_always_bump_minor: Some(false),
                     breaking_always_bump_major: Some(true),
                     initial_tag: None,
+                    version_from_manifest: None,
                     custom_major_increment_regex: None,
                     custom_minor_increment_regex: None,
                     no_increment_regex: None,
                     bump_type: None,
+                    prerelease_identifier: None,
+                    scheme: VersionSchemeType::Semver,
+                    calver_format: None,
+                    rules: vec![],
+                    label_rules: HashMap::new(),
+                    files: vec![],
+                    tag_message: None,
+                    commit_message: None,
+                    sign_tag: None,
+                    base_dir: None,
+                    manifest_version: None,
                 })?
                 .version;
             assert_eq!(expected_version, &next_version);
@@ -450,10 +545,22 @@ mod test {
                 features_always_bump_minor: Some(features_always_bump_minor),
                 breaking_always_bump_major: Some(breaking_always_bump_major),
                 initial_tag: None,
+                version_from_manifest: None,
                 custom_major_increment_regex: None,
                 custom_minor_increment_regex: None,
                 no_increment_regex: None,
                 bump_type: None,
+                prerelease_identifier: None,
+                scheme: VersionSchemeType::Semver,
+                calver_format: None,
+                rules: vec![],
+                label_rules: HashMap::new(),
+                files: vec![],
+                tag_message: None,
+                commit_message: None,
+                sign_tag: None,
+                base_dir: None,
+                manifest_version: None,
             })?;
             assert_eq!("0.1.0", result.version);
             assert_eq!(None, result.bump_type);
@@ -573,6 +680,135 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn bump_prerelease_version() -> Result<()> {
+        fn build_release<'a>(version: &str, commits: &'a [&str]) -> Release<'a> {
+            Release {
+                version: None,
+                commits: commits
+                    .iter()
+                    .map(|v| Commit::from((*v).to_string()))
+                    .collect(),
+                previous: Some(Box::new(Release {
+                    version: Some(String::from(version)),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }
+        }
+        let prerelease = Bump {
+            bump_type: Some(BumpType::Prerelease),
+            prerelease_identifier: Some(String::from("rc")),
+            ..Default::default()
+        };
+        let promote = Bump {
+            bump_type: Some(BumpType::Release),
+            ..Default::default()
+        };
+        let with_identifier = Bump {
+            prerelease_identifier: Some(String::from("rc")),
+            ..Default::default()
+        };
+
+        for (version, commits, config, expected_version) in [
+            ("v1.3.2", vec!["feat: add xyz"], &prerelease, "v1.4.0-rc.1"),
+            ("v1.4.0-rc.1", vec!["fix: abc"], &prerelease, "v1.4.0-rc.2"),
+            ("v1.4.0-rc.2", vec!["fix: abc"], &promote, "v1.4.0"),
+            ("v1.4.0-rc.2", vec!["feat!: abc"], &promote, "v1.4.0"),
+            (
+                "v1.4.0-rc.2",
+                vec!["fix: abc"],
+                &Bump {
+                    prerelease_identifier: Some(String::from("rc")),
+                    ..promote.clone()
+                },
+                "v1.4.0",
+            ),
+            (
+                "v1.4.0-rc.2",
+                vec!["fix: abc"],
+                &with_identifier,
+                "v1.4.0-rc.3",
+            ),
+            ("1.3.2", vec!["fix: abc"], &promote, "1.3.3"),
+            ("1.4.0-beta.3", vec!["fix: abc"], &prerelease, "1.4.0-rc.1"),
+            ("1.3.2", vec!["docs: xyz"], &prerelease, "1.3.3-rc.1"),
+            ("1.3.2", vec!["feat!: xyz"], &prerelease, "2.0.0-rc.1"),
+            (
+                "1.4.0-rc.2",
+                vec!["fix: abc"],
+                &Bump::default(),
+                "1.4.0-rc.3",
+            ),
+        ] {
+            let release = build_release(version, &commits);
+            let result = release.calculate_next_version_with_config(config)?;
+            assert_eq!(expected_version, result.version);
+        }
+
+        let release = build_release("1.4.0-alpha.1", &["fix: abc"]);
+        let result = release.calculate_next_version_with_config(&Bump {
+            bump_type: Some(BumpType::Prerelease),
+            ..Default::default()
+        })?;
+        assert_eq!("1.4.0-alpha.2", result.version);
+        assert_eq!(Some(BumpType::Prerelease), result.bump_type);
+
+        let result = release.calculate_next_version_with_config(&Bump {
+            bump_type: Some(BumpType::Release),
+            ..Default::default()
+        })?;
+        assert_eq!("1.4.0", result.version);
+        assert_eq!(Some(BumpType::Release), result.bump_type);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bump_calver_version() -> Result<()> {
+        fn build_release<'a>(version: Option<&str>, timestamp: i64) -> Release<'a> {
+            let mut commit = Commit::from(String::from("feat: add xyz"));
+            commit.committer.timestamp = timestamp;
+            Release {
+                version: None,
+                commits: vec![commit],
+                previous: Some(Box::new(Release {
+                    version: version.map(String::from),
+                    ..Default::default()
+                })),
+                ..Default::default()
+            }
+        }
+        let config = Bump {
+            scheme: VersionSchemeType::Calver,
+            calver_format: Some(String::from("YYYY.0M.MICRO")),
+            ..Default::default()
+        };
+
+        // 2026-10-18
+        let timestamp = 1_792_281_600;
+        for (version, expected_version) in [
+            (Some("v2026.10.0"), "v2026.10.1"),
+            (Some("v2026.09.3"), "v2026.10.0"),
+            (Some("1.2.3"), "2026.10.0"),
+            (None, "2026.10.0"),
+        ] {
+            let release = build_release(version, timestamp);
+            let result = release.calculate_next_version_with_config(&config)?;
+            assert_eq!(expected_version, result.version);
+            assert_eq!(None, result.bump_type);
+        }
+
+        let release = build_release(None, timestamp);
+        let result = release.calculate_next_version_with_config(&Bump {
+            initial_tag: Some(String::from("2026.01.0")),
+            ..config
+        })?;
+        assert_eq!("2026.01.0", result.version);
+
+        Ok(())
+    }
+
     #[test]
     fn with_statistics() {
         let release = Release {
@@ -634,6 +870,14 @@ mod test {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             github: RemoteReleaseMetadata {
                 contributors: vec![],
             },
@@ -1017,6 +1261,14 @@ mod test {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: RemoteReleaseMetadata {
                 contributors: vec![],
@@ -1403,6 +1655,14 @@ mod test {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: RemoteReleaseMetadata {
                 contributors: vec![],
@@ -1762,6 +2022,14 @@ mod test {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: RemoteReleaseMetadata {
                 contributors: vec![],
@@ -2074,6 +2342,14 @@ mod test {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: RemoteReleaseMetadata {
                 contributors: vec![],
@@ -1,21 +1,25 @@
-use std::io;
+use std::collections::HashMap;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::result::Result as StdResult;
 use std::sync::LazyLock;
 
 use git2::{
-    BranchType, Commit, DescribeOptions, Oid, Repository as GitRepository, Sort, TreeWalkMode,
-    Worktree,
+    BranchType, Commit, DescribeOptions, Mailmap, Oid, Repository as GitRepository,
+    Signature as GitSignature, Sort, TreeWalkMode, Worktree,
 };
 use glob::Pattern;
 use indexmap::IndexMap;
 use regex::Regex;
 use url::Url;
 
-use crate::commit::CommitStatistics;
-use crate::config::Remote;
+use crate::commit::{Commit as AppCommit, CommitStatistics, Signature};
+use crate::config::{CommitDate, Remote};
 use crate::error::{Error, Result};
+use crate::signature::SignatureInfo;
 use crate::tag::Tag;
+use crate::version::VersionScheme;
 
 /// Regex for replacing the signature part of a tag message.
 static TAG_SIGNATURE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
@@ -43,6 +47,14 @@ pub struct Repository {
     path: PathBuf,
     /// Cache path for the changed files of the commits.
     changed_files_cache_path: PathBuf,
+    /// Mailmaps for resolving the author and committer identities.
+    ///
+    /// The first mailmap that resolves an identity takes precedence.
+    mailmaps: Vec<Mailmap>,
+    /// Whether to follow only the first parent of the merge commits.
+    first_parent: bool,
+    /// Range of dates of the selected commits.
+    date_range: DateRange,
 }
 
 /// Range of commits in a submodule.
@@ -54,6 +66,29 @@ pub struct SubmoduleRange {
     pub range: String,
 }
 
+/// Range of dates for selecting commits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    /// Includes the commits at or after this Unix timestamp.
+    pub since: Option<i64>,
+    /// Includes the commits at or before this Unix timestamp.
+    pub until: Option<i64>,
+    /// Date of the commit to compare.
+    pub commit_date: CommitDate,
+}
+
+impl DateRange {
+    /// Returns `true` if the date of the commit is within the range.
+    pub fn contains(&self, commit: &Commit<'_>) -> bool {
+        let time = match self.commit_date {
+            CommitDate::Committer => commit.time(),
+            CommitDate::Author => commit.author().when(),
+        }
+        .seconds();
+        self.since.is_none_or(|since| time >= since) && self.until.is_none_or(|until| time <= until)
+    }
+}
+
 impl Repository {
     /// Opens a repository from the given path.
     ///
@@ -98,10 +133,15 @@ impl Repository {
             .join(env!("CARGO_PKG_NAME"))
             .join(CHANGED_FILES_CACHE);
 
+        let mailmaps = inner.mailmap().ok().into_iter().collect();
+
         Ok(Self {
             inner,
             path,
             changed_files_cache_path,
+            mailmaps,
+            first_parent: false,
+            date_range: DateRange::default(),
         })
     }
 
@@ -147,6 +187,26 @@ impl Repository {
         &self.path
     }
 
+    /// Returns the path of the hooks directory.
+    ///
+    /// Respects the `core.hooksPath` configuration value if it is set.
+    pub fn hooks_path(&self) -> Result<PathBuf> {
+        let hooks_path = self
+            .inner
+            .config()?
+            .get_path("core.hooksPath")
+            .ok()
+            .map(|path| {
+                if path.is_relative() {
+                    self.root_path().map(|root| root.join(path))
+                } else {
+                    Ok(path)
+                }
+            })
+            .transpose()?;
+        Ok(hooks_path.unwrap_or_else(|| self.inner.path().join("hooks")))
+    }
+
     /// Sets the range for the commit search.
     ///
     /// When a single SHA is provided as the range, start from the
@@ -169,12 +229,15 @@ impl Repository {
 
     /// Parses and returns the commits.
     ///
-    /// Sorts the commits by their time.
+    /// Sorts the commits by their time. Only the commits within the date
+    /// range set with [`Repository::set_date_range`] are returned.
     pub fn commits(
         &self,
         range: Option<&str>,
         include_path: Option<Vec<Pattern>>,
         exclude_path: Option<Vec<Pattern>>,
+        include_author: Option<Vec<Pattern>>,
+        exclude_author: Option<Vec<Pattern>>,
         topo_order_commits: bool,
     ) -> Result<Vec<Commit<'_>>> {
         let mut revwalk = self.inner.revwalk()?;
@@ -183,6 +246,9 @@ impl Repository {
         } else {
             revwalk.set_sorting(Sort::TIME)?;
         }
+        if self.first_parent {
+            revwalk.simplify_first_parent()?;
+        }
 
         Self::set_commit_range(&mut revwalk, range).map_err(|e| {
             Error::SetCommitRangeError(range.map_or_else(|| "?".to_string(), String::from), e)
@@ -190,6 +256,7 @@ impl Repository {
         let mut commits: Vec<Commit> = revwalk
             .filter_map(StdResult::ok)
             .filter_map(|id| self.inner.find_commit(id).ok())
+            .filter(|commit| self.date_range.contains(commit))
             .collect();
         if include_path.is_some() || exclude_path.is_some() {
             let include_patterns = include_path.map(|patterns| {
@@ -212,6 +279,11 @@ impl Repository {
                 )
             });
         }
+        if include_author.is_some() || exclude_author.is_some() {
+            commits.retain(|commit| {
+                self.should_retain_author(commit, include_author.as_ref(), exclude_author.as_ref())
+            });
+        }
         Ok(commits)
     }
 
@@ -270,6 +342,133 @@ impl Repository {
         })
     }
 
+    /// Returns the signature information of the given commit.
+    ///
+    /// Returns `None` if the commit is not signed.
+    #[must_use]
+    pub fn commit_signature(&self, commit: &Commit<'_>) -> Option<SignatureInfo> {
+        let (signature, _) = self.inner.extract_signature(&commit.id(), None).ok()?;
+        SignatureInfo::parse(signature.as_str()?)
+    }
+
+    /// Adds a mailmap file for resolving the author and committer identities.
+    ///
+    /// Relative paths are resolved from the root of the repository. The
+    /// entries of the given file take precedence over the repository's
+    /// `.mailmap`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn add_mailmap_file(&mut self, path: &Path) -> Result<()> {
+        let path = self.root_path()?.join(path);
+        let contents = std::fs::read_to_string(&path).map_err(|e| {
+            Error::IoError(io::Error::new(
+                e.kind(),
+                format!("failed to read mailmap file {}: {e}", path.display()),
+            ))
+        })?;
+        self.mailmaps.insert(0, Mailmap::from_buffer(&contents)?);
+        Ok(())
+    }
+
+    /// Sets whether to follow only the first parent of the merge commits.
+    ///
+    /// In this mode, [`Repository::commits`] returns the merge commits
+    /// without the commits they brought in, which can be retrieved with
+    /// [`Repository::merged_commits`].
+    pub fn set_first_parent(&mut self, first_parent: bool) {
+        self.first_parent = first_parent;
+    }
+
+    /// Sets the range of dates of the commits returned by
+    /// [`Repository::commits`].
+    pub fn set_date_range(&mut self, date_range: DateRange) {
+        self.date_range = date_range;
+    }
+
+    /// Returns the commit of the first tag that is a descendant of the given
+    /// commit.
+    ///
+    /// The tags are checked in the given order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ancestry of the commits cannot be checked.
+    pub fn find_descendant_tag(
+        &self,
+        commit: &Commit<'_>,
+        tags: &IndexMap<String, Tag>,
+    ) -> Result<Option<Commit<'_>>> {
+        for id in tags.keys() {
+            let Ok(oid) = Oid::from_str(id) else {
+                continue;
+            };
+            if self.inner.graph_descendant_of(oid, commit.id())? {
+                return Ok(Some(self.inner.find_commit(oid)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the commits that are brought in by the merge commit.
+    ///
+    /// These are the commits reachable from the other parents but not from
+    /// the first parent, sorted by their time. Returns an empty list for the
+    /// commits with a single parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history of the parents cannot be walked.
+    pub fn merged_commits(&self, commit: &Commit<'_>) -> Result<Vec<Commit<'_>>> {
+        if commit.parent_count() < 2 {
+            return Ok(Vec::new());
+        }
+        let mut revwalk = self.inner.revwalk()?;
+        revwalk.set_sorting(Sort::TIME)?;
+        for parent_id in commit.parent_ids().skip(1) {
+            revwalk.push(parent_id)?;
+        }
+        revwalk.hide(commit.parent_id(0)?)?;
+        Ok(revwalk
+            .filter_map(StdResult::ok)
+            .filter_map(|id| self.inner.find_commit(id).ok())
+            .collect())
+    }
+
+    /// Resolves the given identity using the mailmap.
+    ///
+    /// Returns the identity as-is if it is not mapped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identity cannot be resolved.
+    pub fn resolve_signature(&self, signature: &GitSignature<'_>) -> Result<Signature> {
+        for mailmap in &self.mailmaps {
+            let resolved = mailmap.resolve_signature(signature)?;
+            if resolved.name_bytes() != signature.name_bytes() ||
+                resolved.email_bytes() != signature.email_bytes()
+            {
+                return Ok(resolved.into());
+            }
+        }
+        Ok(signature.to_owned().into())
+    }
+
+    /// Resolves the author and committer of the commit using the mailmap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the identities cannot be resolved.
+    pub fn apply_mailmap(&self, git_commit: &Commit<'_>, commit: &mut AppCommit<'_>) -> Result<()> {
+        if self.mailmaps.is_empty() {
+            return Ok(());
+        }
+        commit.author = self.resolve_signature(&git_commit.author())?;
+        commit.committer = self.resolve_signature(&git_commit.committer())?;
+        Ok(())
+    }
+
     /// Returns submodule repositories for a given commit range.
     ///
     /// For one or two given commits in this repository, a list of changed
@@ -391,6 +590,24 @@ impl Repository {
         }
     }
 
+    /// Calculates whether the commit should be retained based on its author.
+    ///
+    /// The patterns are matched against the name, the email and the
+    /// `Name <email>` form of the author, which is resolved using the
+    /// mailmap.
+    fn should_retain_author(
+        &self,
+        commit: &Commit,
+        include_patterns: Option<&Vec<Pattern>>,
+        exclude_patterns: Option<&Vec<Pattern>>,
+    ) -> bool {
+        let author = self
+            .resolve_signature(&commit.author())
+            .unwrap_or_else(|_| commit.author().into());
+        include_patterns.is_none_or(|patterns| author.matches(patterns)) &&
+            !exclude_patterns.is_some_and(|patterns| author.matches(patterns))
+    }
+
     /// Returns the changed files of the commit.
     ///
     /// It uses a cache to speed up checks to store the changed files of the
@@ -399,7 +616,7 @@ impl Repository {
     ///
     /// If the cache is not found, it calculates the changed files and adds them
     /// to the cache via [`Self::commit_changed_files_no_cache`].
-    fn commit_changed_files(&self, commit: &Commit) -> Vec<PathBuf> {
+    pub fn commit_changed_files(&self, commit: &Commit) -> Vec<PathBuf> {
         // Cache key is generated from the repository path and commit id
         let cache_key = format!("commit_id:{}", commit.id());
 
@@ -513,19 +730,179 @@ impl Repository {
             .resolve_reference_from_short_name(name)
             .and_then(|r| r.peel_to_tag())
         {
-            Ok(tag) => Tag {
-                name: tag.name().unwrap_or_default().to_owned(),
-                message: tag
-                    .message()
-                    .map(|msg| TAG_SIGNATURE_REGEX.replace(msg, "").trim().to_owned()),
-            },
+            Ok(tag) => annotated_tag(
+                tag.name().unwrap_or_default().to_owned(),
+                tag.message(),
+                tag.tagger(),
+            ),
             _ => Tag {
                 name: name.to_owned(),
-                message: None,
+                ..Default::default()
             },
         }
     }
 
+    /// Returns whether the index has changes that are not committed yet.
+    pub fn is_index_dirty(&self) -> Result<bool> {
+        let head_tree = self
+            .inner
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_tree().ok());
+        let diff = self
+            .inner
+            .diff_tree_to_index(head_tree.as_ref(), None, None)?;
+        Ok(diff.deltas().len() != 0)
+    }
+
+    /// Stages the given files and commits them on top of the HEAD commit.
+    pub fn commit_files(&self, paths: &[PathBuf], message: &str) -> Result<Oid> {
+        let root = std::fs::canonicalize(self.root_path()?)?;
+        let mut index = self.inner.index()?;
+        for path in paths {
+            let path = std::fs::canonicalize(path)?;
+            let relative_path = path.strip_prefix(&root).map_err(|_| {
+                Error::RepoError(format!(
+                    "{} is not in the repository: {}",
+                    path.display(),
+                    root.display()
+                ))
+            })?;
+            index.add_path(relative_path)?;
+        }
+        index.write()?;
+        let tree = self.inner.find_tree(index.write_tree()?)?;
+        let signature = self.inner.signature()?;
+        let parents = match self.inner.head() {
+            Ok(head) => vec![head.peel_to_commit()?],
+            Err(_) => vec![],
+        };
+        Ok(self.inner.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents.iter().collect::<Vec<&Commit<'_>>>(),
+        )?)
+    }
+
+    /// Creates an annotated tag for the HEAD commit with the given message.
+    ///
+    /// If `sign` is not set, the tag is signed if `tag.gpgSign` is enabled in
+    /// the git configuration.
+    pub fn create_tag(&self, name: &str, message: &str, sign: Option<bool>) -> Result<Oid> {
+        if self
+            .inner
+            .refname_to_id(&format!("refs/tags/{name}"))
+            .is_ok()
+        {
+            return Err(Error::RepoError(format!("Tag already exists: {name}")));
+        }
+        let target = self.inner.head()?.peel_to_commit()?;
+        let tagger = self.inner.signature()?;
+        let sign = match sign {
+            Some(sign) => sign,
+            None => self
+                .inner
+                .config()?
+                .get_bool("tag.gpgSign")
+                .unwrap_or(false),
+        };
+        if !sign {
+            return Ok(self
+                .inner
+                .tag(name, target.as_object(), &tagger, message, false)?);
+        }
+
+        // https://git-scm.com/docs/gitformat-signature#_tags
+        let time = tagger.when();
+        let offset = time.offset_minutes();
+        let mut buffer = format!(
+            "object {}\ntype commit\ntag {name}\ntagger {} <{}> {} {}{:02}{:02}\n\n{message}",
+            target.id(),
+            tagger.name().unwrap_or_default(),
+            tagger.email().unwrap_or_default(),
+            time.seconds(),
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 60,
+            offset.abs() % 60,
+        );
+        if !buffer.ends_with('\n') {
+            buffer.push('\n');
+        }
+        buffer.push_str(&self.sign_buffer(&buffer, &tagger)?);
+        let oid = self
+            .inner
+            .odb()?
+            .write(git2::ObjectType::Tag, buffer.as_bytes())?;
+        self.inner
+            .reference(&format!("refs/tags/{name}"), oid, false, "git-cliff: tag")?;
+        Ok(oid)
+    }
+
+    /// Signs the given buffer via the signing program in the git
+    /// configuration and returns the ASCII armored signature.
+    ///
+    /// <https://git-scm.com/docs/git-config#Documentation/git-config.txt-gpgformat>
+    fn sign_buffer(&self, buffer: &str, signer: &GitSignature<'_>) -> Result<String> {
+        let config = self.inner.config()?;
+        let format = config
+            .get_string("gpg.format")
+            .unwrap_or_else(|_| String::from("openpgp"));
+        let signing_key = config.get_string("user.signingKey").ok();
+        let program = |key: &str, default: &str| {
+            config
+                .get_string(key)
+                .or_else(|_| config.get_string("gpg.program"))
+                .unwrap_or_else(|_| default.to_string())
+        };
+        let mut command = match format.as_str() {
+            "ssh" => {
+                let key = signing_key.ok_or_else(|| {
+                    Error::RepoError(String::from(
+                        "user.signingKey must be set for signing with SSH",
+                    ))
+                })?;
+                let mut command = Command::new(
+                    config
+                        .get_string("gpg.ssh.program")
+                        .unwrap_or_else(|_| String::from("ssh-keygen")),
+                );
+                command.args(["-Y", "sign", "-n", "git", "-f", &key]);
+                command
+            }
+            format => {
+                let mut command = Command::new(if format == "x509" {
+                    program("gpg.x509.program", "gpgsm")
+                } else {
+                    program("gpg.openpgp.program", "gpg")
+                });
+                let key = signing_key.unwrap_or_else(|| signer.to_string());
+                command.args(["--status-fd=2", "-bsau", &key]);
+                command
+            }
+        };
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| io::Error::other("stdin is not captured"))?
+            .write_all(buffer.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(Error::RepoError(format!(
+                "Failed to sign the tag: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8(output.stdout).map_err(|e| e.utf8_error())?)
+    }
+
     /// Returns the commit object of the given ID.
     #[must_use]
     pub fn find_commit(&self, id: &str) -> Option<Commit<'_>> {
@@ -552,12 +929,16 @@ impl Repository {
 
     /// Parses and returns a commit-tag map.
     ///
-    /// It collects lightweight and annotated tags.
+    /// It collects lightweight and annotated tags. If a versioning scheme is
+    /// given, the tags are sorted by it so the tag with the highest version is
+    /// used when a commit has multiple tags. The tags are then sorted by time
+    /// unless `topo_order` is set.
     pub fn tags(
         &self,
         pattern: &Option<Regex>,
         topo_order: bool,
         use_branch_tags: bool,
+        version_scheme: Option<&dyn VersionScheme>,
     ) -> Result<IndexMap<String, Tag>> {
         let mut tags: Vec<(Commit, Tag)> = Vec::new();
         let tag_names = self.inner.tag_names(None)?;
@@ -576,7 +957,7 @@ impl Repository {
 
                 tags.push((commit, Tag {
                     name,
-                    message: None,
+                    ..Default::default()
                 }));
             } else if let Some(tag) = obj.as_tag() {
                 // Use peel to resolve nested tags to the final commit
@@ -588,15 +969,20 @@ impl Repository {
                     if use_branch_tags && !self.should_include_tag(&head_commit, &commit)? {
                         continue;
                     }
-                    tags.push((commit, Tag {
-                        name: tag.name().map(String::from).unwrap_or(name),
-                        message: tag
-                            .message()
-                            .map(|msg| TAG_SIGNATURE_REGEX.replace(msg, "").trim().to_owned()),
-                    }));
+                    tags.push((
+                        commit,
+                        annotated_tag(
+                            tag.name().map(String::from).unwrap_or(name),
+                            tag.message(),
+                            tag.tagger(),
+                        ),
+                    ));
                 }
             }
         }
+        if let Some(version_scheme) = version_scheme {
+            tags.sort_by(|a, b| version_scheme.compare_str(&a.1.name, &b.1.name));
+        }
         if !topo_order {
             tags.sort_by_key(|a| a.0.time().seconds());
         }
@@ -606,6 +992,44 @@ impl Repository {
             .collect())
     }
 
+    /// Returns the name of the current branch.
+    ///
+    /// Returns `None` if HEAD is detached.
+    pub fn current_branch(&self) -> Option<String> {
+        self.inner
+            .head()
+            .ok()
+            .filter(git2::Reference::is_branch)
+            .and_then(|head| head.shorthand().map(String::from))
+    }
+
+    /// Returns the index of the tag that each commit is released with.
+    ///
+    /// The commits of a tag are the commits that are reachable from the tag
+    /// but not from the previous tag in the given order (i.e. the commits since
+    /// the merge base of the tags). The commits that are released with an
+    /// earlier tag are not included again.
+    pub fn tag_commit_indices(
+        &self,
+        tags: &IndexMap<String, Tag>,
+    ) -> Result<HashMap<String, usize>> {
+        let mut indices = HashMap::new();
+        let mut previous: Option<Oid> = None;
+        for (index, commit_id) in tags.keys().enumerate() {
+            let oid = Oid::from_str(commit_id)?;
+            let mut revwalk = self.inner.revwalk()?;
+            revwalk.push(oid)?;
+            if let Some(previous) = previous {
+                revwalk.hide(previous)?;
+            }
+            for id in revwalk.filter_map(StdResult::ok) {
+                indices.entry(id.to_string()).or_insert(index);
+            }
+            previous = Some(oid);
+        }
+        Ok(indices)
+    }
+
     /// Returns the remote of the upstream repository.
     ///
     /// The strategy used here is the following:
@@ -642,6 +1066,26 @@ impl Repository {
     }
 }
 
+/// Creates an annotated tag from the given name, message and tagger.
+///
+/// The signature is removed from the message and parsed separately.
+fn annotated_tag(name: String, message: Option<&str>, tagger: Option<git2::Signature>) -> Tag {
+    let tagger = tagger.map(Signature::from);
+    let mut tag = Tag {
+        name,
+        message: message.map(|msg| TAG_SIGNATURE_REGEX.replace(msg, "").trim().to_owned()),
+        timestamp: tagger.as_ref().map(|tagger| tagger.timestamp),
+        tagger,
+        ..Default::default()
+    };
+    tag.set_signature(
+        message
+            .and_then(|msg| TAG_SIGNATURE_REGEX.find(msg))
+            .and_then(|signature| SignatureInfo::parse(signature.as_str())),
+    );
+    tag
+}
+
 fn find_remote(url: &str) -> Result<Remote> {
     url_path_segments(url).or_else(|err| {
         if url.contains('@') && url.contains(':') && url.contains('/') {
@@ -723,6 +1167,8 @@ mod test {
 
     use super::*;
     use crate::commit::Commit as AppCommit;
+    use crate::signature::SignatureType;
+    use crate::version::{Pep440, Semver};
 
     fn get_last_commit_hash() -> Result<String> {
         Ok(str::from_utf8(
@@ -769,6 +1215,77 @@ mod test {
         )
     }
 
+    #[test]
+    fn commits_in_date_range() -> Result<()> {
+        let repository = get_repository()?;
+        let commit = repository
+            .inner
+            .find_commit(Oid::from_str(&get_last_commit_hash()?)?)?;
+        let time = commit.time().seconds();
+        assert!(DateRange::default().contains(&commit));
+        assert!(
+            DateRange {
+                since: Some(time),
+                until: Some(time),
+                ..Default::default()
+            }
+            .contains(&commit)
+        );
+        assert!(
+            !DateRange {
+                since: Some(time + 1),
+                ..Default::default()
+            }
+            .contains(&commit)
+        );
+        assert!(
+            !DateRange {
+                until: Some(commit.author().when().seconds() - 1),
+                commit_date: CommitDate::Author,
+                ..Default::default()
+            }
+            .contains(&commit)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn filters_commits_by_date() -> Result<()> {
+        let (mut repo, temp_dir) = create_temp_repo();
+        for (message, date) in [
+            ("feat: one", "2025-01-01T12:00:00Z"),
+            ("feat: two", "2025-02-01T12:00:00Z"),
+            ("feat: three", "2025-03-01T12:00:00Z"),
+        ] {
+            let output = Command::new("git")
+                .args(["commit", "--no-gpg-sign", "--allow-empty", "-m", message])
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .current_dir(temp_dir.path())
+                .output()?;
+            assert!(output.status.success(), "git commit failed {output:?}");
+        }
+        repo.set_date_range(DateRange {
+            since: Some(1_738_000_000),
+            ..Default::default()
+        });
+        let summaries = |repo: &Repository| -> Result<Vec<String>> {
+            Ok(repo
+                .commits(None, None, None, None, None, false)?
+                .iter()
+                .filter_map(|commit| commit.summary().map(String::from))
+                .collect())
+        };
+        assert_eq!(vec!["feat: three", "feat: two"], summaries(&repo)?);
+        repo.set_date_range(DateRange {
+            since: Some(1_738_000_000),
+            until: Some(1_740_000_000),
+            ..Default::default()
+        });
+        assert_eq!(vec!["feat: two"], summaries(&repo)?);
+        Ok(())
+    }
+
     #[test]
     fn http_url_repo_owner() -> Result<()> {
         let url = "https://hostname.com/bob/magic.git";
@@ -790,7 +1307,7 @@ mod test {
     #[test]
     fn get_latest_commit() -> Result<()> {
         let repository = get_repository()?;
-        let commits = repository.commits(None, None, None, false)?;
+        let commits = repository.commits(None, None, None, None, None, false)?;
         let last_commit = AppCommit::from(&commits.first().expect("no commits found").clone());
         assert_eq!(get_last_commit_hash()?, last_commit.id);
         Ok(())
@@ -810,7 +1327,7 @@ mod test {
     #[test]
     fn get_latest_tag() -> Result<()> {
         let repository = get_repository()?;
-        let tags = repository.tags(&None, false, false)?;
+        let tags = repository.tags(&None, false, false, None)?;
         let latest = tags.last().expect("no tags found").1.name.clone();
         assert_eq!(get_last_tag()?, latest);
 
@@ -822,7 +1339,7 @@ mod test {
     #[test]
     fn git_tags() -> Result<()> {
         let repository = get_repository()?;
-        let tags = repository.tags(&None, true, false)?;
+        let tags = repository.tags(&None, true, false, None)?;
         assert_eq!(
             tags.get("2b8b4d3535f29231e05c3572e919634b9af907b6")
                 .expect("the commit hash does not exist in the repository (tag v0.1.0)")
@@ -839,6 +1356,7 @@ mod test {
             &Some(Regex::new("^v[0-9]+\\.[0-9]+\\.[0-9]$").expect("the regex is not valid")),
             true,
             false,
+            None,
         )?;
         assert_eq!(
             tags.get("2b8b4d3535f29231e05c3572e919634b9af907b6")
@@ -868,7 +1386,7 @@ mod test {
             .current_dir(path)
             .output()?;
 
-        let tags = repo.tags(&Some(Regex::new("v1.0.0-stable")?), false, false)?;
+        let tags = repo.tags(&Some(Regex::new("v1.0.0-stable")?), false, false, None)?;
         assert_eq!(
             tags.get(&commit.id().to_string())
                 .expect("nested tag should resolve to commit")
@@ -928,7 +1446,7 @@ mod test {
         let repository = get_repository()?;
         // a close descendant of the root commit
         let range = Some("eea3914c7ab07472841aa85c36d11bdb2589a234");
-        let commits = repository.commits(range, None, None, false)?;
+        let commits = repository.commits(range, None, None, None, None, false)?;
         let root_commit = AppCommit::from(&commits.last().expect("no commits found").clone());
         assert_eq!(get_root_commit_hash()?, root_commit.id);
         Ok(())
@@ -1156,7 +1674,7 @@ mod test {
         )]);
 
         let mut commits = repo
-            .commits(None, None, None, false)
+            .commits(None, None, None, None, None, false)
             .expect("failed to get commits");
         assert_eq!(commits.len(), 4, "sanity check before filtering");
 
@@ -1182,7 +1700,7 @@ mod test {
         create_commit_with_files(&repo, vec![("file1.txt", "content1")]);
 
         let mut commits = repo
-            .commits(None, None, None, false)
+            .commits(None, None, None, None, None, false)
             .expect("failed to get commits");
         let before = commits.len();
 
@@ -1195,6 +1713,402 @@ mod test {
         );
     }
 
+    #[test]
+    fn parses_commit_and_tag_signatures() -> Result<()> {
+        let (repo, _temp_dir) = create_temp_repo();
+        let unsigned_commit = create_commit_with_files(&repo, vec![("file1.txt", "content1")]);
+        assert_eq!(None, repo.commit_signature(&unsigned_commit));
+
+        let signature = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAg2o5fThBkxAdzlVNRgYGYmqEIfm
+GL8VKSj9xCvmT/00AAAAADZ2l0AAAAAAAAAAZzaGE1MTIAAABTAAAAC3NzaC1lZDI1NTE5
+AAAAQJ/WhXgmq1sbMCcYoX3il3Hhpl3noLWHncfo2VG+OGsf3TCMdblduXZ7EwltiA9NoF
+IIYSJDBRmdzltkbVxtuQg=
+-----END SSH SIGNATURE-----";
+        let author = git2::Signature::now("test", "test@gmail.com")?;
+        let buffer = repo.inner.commit_create_buffer(
+            &author,
+            &author,
+            "signed commit",
+            &unsigned_commit.tree()?,
+            &[&unsigned_commit],
+        )?;
+        let oid = repo
+            .inner
+            .commit_signed(buffer.as_str().unwrap_or_default(), signature, None)?;
+        let signed_commit = repo.inner.find_commit(oid)?;
+        let expected_signature = SignatureInfo {
+            signature_type: SignatureType::Ssh,
+            signer_key: Some(String::from(
+                "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAINqOX04QZMQHc5VTUYGBmJqhCH5hi/FSko/cQr5k/9NA",
+            )),
+        };
+        assert_eq!(
+            Some(expected_signature.clone()),
+            repo.commit_signature(&signed_commit)
+        );
+
+        repo.inner.tag(
+            "v1.0.0",
+            signed_commit.as_object(),
+            &author,
+            &format!("Release v1.0.0\n{signature}\n"),
+            false,
+        )?;
+        let tags = repo.tags(&None, false, false, None)?;
+        let tag = tags.get(&oid.to_string()).expect("tag should exist");
+        assert_eq!(Some(String::from("Release v1.0.0")), tag.message);
+        assert!(tag.signed);
+        assert_eq!(Some(SignatureType::Ssh), tag.signature_type);
+        assert_eq!(expected_signature.signer_key, tag.signer_key);
+        Ok(())
+    }
+
+    #[test]
+    fn sorts_tags_by_version_scheme() -> Result<()> {
+        let (repo, _temp_dir) = create_temp_repo();
+        let commit = create_commit_with_files(&repo, vec![("file1.txt", "content1")]);
+        for name in ["v1.0.0", "v1.0.0-rc.1", "v1.0.0rc2"] {
+            repo.inner
+                .tag_lightweight(name, commit.as_object(), false)?;
+        }
+        let tags = repo.tags(&None, false, false, Some(&Semver))?;
+        let tag = tags
+            .get(&commit.id().to_string())
+            .expect("tag should exist");
+        assert_eq!("v1.0.0", tag.name);
+
+        repo.inner
+            .tag_lightweight("v1.0.0.post1", commit.as_object(), false)?;
+        let tags = repo.tags(&None, true, false, Some(&Pep440))?;
+        let tag = tags
+            .get(&commit.id().to_string())
+            .expect("tag should exist");
+        assert_eq!("v1.0.0.post1", tag.name);
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_tag_name_order_in_topo_order() -> Result<()> {
+        let (repo, _temp_dir) = create_temp_repo();
+        let v0_9_0 = create_commit_with_files(&repo, vec![("file1.txt", "content1")]);
+        repo.inner
+            .tag_lightweight("v0.9.0", v0_9_0.as_object(), false)?;
+        let v0_10_0 = create_commit_with_files(&repo, vec![("file2.txt", "content2")]);
+        repo.inner
+            .tag_lightweight("v0.10.0", v0_10_0.as_object(), false)?;
+        let names = |tags: IndexMap<String, Tag>| -> Vec<String> {
+            tags.into_values().map(|tag| tag.name).collect()
+        };
+        assert_eq!(
+            vec!["v0.10.0", "v0.9.0"],
+            names(repo.tags(&None, true, false, None)?)
+        );
+        assert_eq!(
+            vec!["v0.9.0", "v0.10.0"],
+            names(repo.tags(&None, true, false, Some(&Semver))?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn assigns_commits_to_tags_ordered_by_version() -> Result<()> {
+        let (repo, _temp_dir) = create_temp_repo();
+        let v1_0_0 = create_commit_with_files(&repo, vec![("file1.txt", "content1")]);
+        repo.inner
+            .tag_lightweight("v1.0.0", v1_0_0.as_object(), false)?;
+        let v2_0_0 = create_commit_with_files(&repo, vec![("file2.txt", "content2")]);
+        repo.inner
+            .tag_lightweight("v2.0.0", v2_0_0.as_object(), false)?;
+        let output = Command::new("git")
+            .args(["checkout", "-b", "release/1.x", "v1.0.0"])
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to execute git checkout");
+        assert!(output.status.success(), "git checkout failed {output:?}");
+        let v1_0_1 = create_commit_with_files(&repo, vec![("file3.txt", "content3")]);
+        repo.inner
+            .tag_lightweight("v1.0.1", v1_0_1.as_object(), false)?;
+
+        let tags = repo.tags(&None, true, false, Some(&Semver))?;
+        assert_eq!(
+            vec!["v1.0.0", "v1.0.1", "v2.0.0"],
+            tags.values()
+                .map(|tag| tag.name.as_str())
+                .collect::<Vec<&str>>()
+        );
+        let indices = repo.tag_commit_indices(&tags)?;
+        assert_eq!(Some(&0), indices.get(&v1_0_0.id().to_string()));
+        assert_eq!(Some(&1), indices.get(&v1_0_1.id().to_string()));
+        assert_eq!(Some(&2), indices.get(&v2_0_0.id().to_string()));
+        assert_eq!(1, indices.values().filter(|index| **index == 2).count());
+        Ok(())
+    }
+
+    #[test]
+    fn commits_files() -> Result<()> {
+        let (repo, temp_dir) = create_temp_repo();
+        let parent = create_commit_with_files(&repo, vec![("CHANGELOG.md", "")]);
+        fs::write(temp_dir.path().join("CHANGELOG.md"), "# Changelog")?;
+        fs::write(temp_dir.path().join("VERSION"), "1.0.0")?;
+        fs::write(temp_dir.path().join("untracked.txt"), "")?;
+        assert!(!repo.is_index_dirty()?);
+
+        let oid = repo.commit_files(
+            &[
+                temp_dir.path().join("CHANGELOG.md"),
+                temp_dir.path().join("VERSION"),
+            ],
+            "chore(release): 1.0.0",
+        )?;
+        let commit = repo.inner.find_commit(oid)?;
+        assert_eq!(Some("chore(release): 1.0.0"), commit.message());
+        assert_eq!(oid, repo.inner.head()?.peel_to_commit()?.id());
+        assert_eq!(vec![parent.id()], commit.parent_ids().collect::<Vec<Oid>>());
+        let diff =
+            repo.inner
+                .diff_tree_to_tree(Some(&parent.tree()?), Some(&commit.tree()?), None)?;
+        assert_eq!(
+            vec![Path::new("CHANGELOG.md"), Path::new("VERSION")],
+            diff.deltas()
+                .filter_map(|delta| delta.new_file().path())
+                .collect::<Vec<&Path>>()
+        );
+        assert!(!repo.is_index_dirty()?);
+
+        let mut index = repo.inner.index()?;
+        index.add_path(Path::new("untracked.txt"))?;
+        index.write()?;
+        assert!(repo.is_index_dirty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn creates_annotated_tag() -> Result<()> {
+        let (repo, _temp_dir) = create_temp_repo();
+        let commit = create_commit_with_files(&repo, vec![("test.txt", "test")]);
+
+        repo.create_tag("v1.0.0", "Release v1.0.0\n\n- add test", Some(false))?;
+        let tag = repo.resolve_tag("v1.0.0");
+        assert_eq!("v1.0.0", tag.name);
+        assert_eq!(
+            Some(String::from("Release v1.0.0\n\n- add test")),
+            tag.message
+        );
+        assert_eq!(
+            Some(commit.id().to_string()),
+            repo.tags(&None, false, false, None)?
+                .first()
+                .map(|(id, _)| id.clone())
+        );
+        let tag_object = repo
+            .inner
+            .revparse_single("refs/tags/v1.0.0")?
+            .into_tag()
+            .expect("tag should be annotated");
+        assert_eq!(commit.id(), tag_object.target_id());
+        assert_eq!(
+            Some("test"),
+            tag_object
+                .tagger()
+                .and_then(|t| t.name().map(String::from))
+                .as_deref()
+        );
+        assert!(!tag.signed);
+
+        assert!(matches!(
+            repo.create_tag("v1.0.0", "", Some(false)),
+            Err(Error::RepoError(_))
+        ));
+
+        let output = Command::new("git")
+            .args(["config", "tag.gpgSign", "false"])
+            .current_dir(&repo.path)
+            .output()?;
+        assert!(output.status.success(), "git config failed {output:?}");
+        repo.create_tag("v1.0.1", "Release v1.0.1", None)?;
+        let tag = repo.resolve_tag("v1.0.1");
+        assert_eq!(Some(String::from("Release v1.0.1")), tag.message);
+        assert!(!tag.signed);
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_tagger_of_annotated_tag() -> Result<()> {
+        let (repo, _temp_dir) = create_temp_repo();
+        let commit = create_commit_with_files(&repo, vec![("test.txt", "test")]);
+        let tagger = git2::Signature::new(
+            "Tagger",
+            "tagger@example.com",
+            &git2::Time::new(1_700_000_000, 0),
+        )?;
+        repo.inner.tag(
+            "v1.0.0",
+            commit.as_object(),
+            &tagger,
+            "Release v1.0.0",
+            false,
+        )?;
+        repo.inner
+            .tag_lightweight("v1.0.1", commit.as_object(), false)?;
+
+        let tag = repo.resolve_tag("v1.0.0");
+        assert_eq!(
+            Some(Signature {
+                name: Some(String::from("Tagger")),
+                email: Some(String::from("tagger@example.com")),
+                timestamp: 1_700_000_000,
+            }),
+            tag.tagger
+        );
+        assert_eq!(Some(1_700_000_000), tag.timestamp);
+        let tag = repo.resolve_tag("v1.0.1");
+        assert_eq!(None, tag.tagger);
+        assert_eq!(None, tag.timestamp);
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_identities_with_mailmap() -> Result<()> {
+        let (repo, temp_dir) = create_temp_repo();
+        let commit_id = create_commit_with_files(&repo, vec![(
+            ".mailmap",
+            "Proper Name <proper@example.com> <test@gmail.com>\n",
+        )])
+        .id()
+        .to_string();
+        fs::write(
+            temp_dir.path().join("extra.mailmap"),
+            "Other Name <other@example.com> <test@gmail.com>\n",
+        )?;
+
+        let repo = Repository::discover(temp_dir.path().to_path_buf())?;
+        let git_commit = repo.find_commit(&commit_id).expect("commit should exist");
+        let mut commit = AppCommit::from(&git_commit);
+        repo.apply_mailmap(&git_commit, &mut commit)?;
+        assert_eq!(Some(String::from("Proper Name")), commit.author.name);
+        assert_eq!(
+            Some(String::from("proper@example.com")),
+            commit.author.email
+        );
+        assert_eq!(
+            git_commit.author().when().seconds(),
+            commit.author.timestamp
+        );
+        assert_eq!(commit.author, commit.committer);
+
+        let mut repo = Repository::discover(temp_dir.path().to_path_buf())?;
+        repo.add_mailmap_file(Path::new("extra.mailmap"))?;
+        assert!(repo.add_mailmap_file(Path::new("missing.mailmap")).is_err());
+        let git_commit = repo.find_commit(&commit_id).expect("commit should exist");
+        let mut commit = AppCommit::from(&git_commit);
+        repo.apply_mailmap(&git_commit, &mut commit)?;
+        assert_eq!(Some(String::from("Other Name")), commit.author.name);
+        assert_eq!(Some(String::from("other@example.com")), commit.author.email);
+        Ok(())
+    }
+
+    #[test]
+    fn filters_commits_by_author() -> Result<()> {
+        let (repo, temp_dir) = create_temp_repo();
+        create_commit_with_files(&repo, vec![(
+            ".mailmap",
+            "Proper Name <proper@example.com> <test@gmail.com>\n",
+        )]);
+        let output = Command::new("git")
+            .args([
+                "commit",
+                "--no-gpg-sign",
+                "--allow-empty",
+                "--author",
+                "Other Name <other@example.org>",
+                "-m",
+                "other commit",
+            ])
+            .current_dir(temp_dir.path())
+            .output()?;
+        assert!(output.status.success(), "git commit failed {output:?}");
+
+        let repo = Repository::discover(temp_dir.path().to_path_buf())?;
+        let authors = |include: Option<&str>, exclude: Option<&str>| -> Result<Vec<String>> {
+            let patterns = |pattern: Option<&str>| {
+                pattern.map(|pattern| vec![Pattern::new(pattern).expect("valid pattern")])
+            };
+            Ok(repo
+                .commits(
+                    None,
+                    None,
+                    None,
+                    patterns(include),
+                    patterns(exclude),
+                    false,
+                )?
+                .iter()
+                .filter_map(|commit| commit.summary().map(String::from))
+                .collect())
+        };
+        assert_eq!(vec!["other commit"], authors(Some("Other*"), None)?);
+        assert_eq!(vec!["test commit"], authors(Some("*@example.com"), None)?);
+        assert_eq!(
+            vec!["test commit"],
+            authors(Some("Proper Name <proper@example.com>"), None)?
+        );
+        assert_eq!(vec!["test commit"], authors(None, Some("other@*"))?);
+        assert!(authors(Some("test"), None)?.is_empty());
+        assert!(authors(Some("Other*"), Some("*.org"))?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn walks_first_parent_history() -> Result<()> {
+        let (repo, temp_dir) = create_temp_repo();
+        let git = |args: &[&str]| -> Result<()> {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(temp_dir.path())
+                .output()?;
+            assert!(output.status.success(), "git failed {output:?}");
+            Ok(())
+        };
+        create_commit_with_files(&repo, vec![("file1.txt", "content1")]);
+        git(&["checkout", "-b", "feature"])?;
+        git(&[
+            "commit",
+            "--no-gpg-sign",
+            "--allow-empty",
+            "-m",
+            "feat: one",
+        ])?;
+        git(&["commit", "--no-gpg-sign", "--allow-empty", "-m", "fix: two"])?;
+        git(&["checkout", "-"])?;
+        git(&[
+            "merge",
+            "--no-gpg-sign",
+            "--no-ff",
+            "feature",
+            "-m",
+            "Merge feature",
+        ])?;
+
+        let mut repo = Repository::discover(temp_dir.path().to_path_buf())?;
+        let summaries = |commits: &[Commit<'_>]| -> Vec<String> {
+            commits
+                .iter()
+                .filter_map(|commit| commit.summary().map(String::from))
+                .collect()
+        };
+        assert_eq!(4, repo.commits(None, None, None, None, None, false)?.len());
+        repo.set_first_parent(true);
+        let commits = repo.commits(None, None, None, None, None, false)?;
+        assert_eq!(vec!["Merge feature", "test commit"], summaries(&commits));
+        assert_eq!(
+            vec!["fix: two", "feat: one"],
+            summaries(&repo.merged_commits(&commits[0])?)
+        );
+        assert!(repo.merged_commits(&commits[1])?.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_normalize_pattern() {
         let normalize = |input: &str| {
@@ -0,0 +1,384 @@
+use std::fmt;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize};
+
+/// OpenPGP packet type of a signature.
+const PGP_SIGNATURE_PACKET: u8 = 2;
+/// OpenPGP signature subpacket type of the issuer key ID.
+const PGP_ISSUER_SUBPACKET: u8 = 16;
+/// OpenPGP signature subpacket type of the issuer fingerprint.
+const PGP_ISSUER_FINGERPRINT_SUBPACKET: u8 = 33;
+/// Magic preamble of an SSH signature.
+///
+/// <https://github.com/openssh/openssh-portable/blob/master/PROTOCOL.sshsig>
+const SSH_SIGNATURE_MAGIC: &[u8] = b"SSHSIG";
+
+/// Type of a commit or tag signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureType {
+    /// OpenPGP signature.
+    Gpg,
+    /// SSH signature.
+    Ssh,
+    /// X.509 (S/MIME) signature.
+    X509,
+}
+
+impl fmt::Display for SignatureType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Gpg => write!(f, "gpg"),
+            Self::Ssh => write!(f, "ssh"),
+            Self::X509 => write!(f, "x509"),
+        }
+    }
+}
+
+/// Information about a commit or tag signature.
+///
+/// The signature is not verified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureInfo {
+    /// Type of the signature.
+    pub signature_type: SignatureType,
+    /// Key that is used for signing.
+    ///
+    /// For GPG signatures, this is the fingerprint (or the key ID) of the
+    /// issuer. For SSH signatures, this is the public key in the
+    /// `<type> <base64>` format.
+    pub signer_key: Option<String>,
+}
+
+impl SignatureInfo {
+    /// Parses the given ASCII armored signature.
+    ///
+    /// Returns `None` if the signature type is not recognized.
+    #[must_use]
+    pub fn parse(signature: &str) -> Option<Self> {
+        let mut lines = signature.lines().map(str::trim);
+        let signature_type = match lines.next()? {
+            "-----BEGIN PGP SIGNATURE-----" | "-----BEGIN PGP MESSAGE-----" => SignatureType::Gpg,
+            "-----BEGIN SSH SIGNATURE-----" => SignatureType::Ssh,
+            "-----BEGIN SIGNED MESSAGE-----" => SignatureType::X509,
+            _ => return None,
+        };
+        let signer_key = decode_armor(lines).and_then(|data| match signature_type {
+            SignatureType::Gpg => pgp_issuer(&data),
+            SignatureType::Ssh => ssh_public_key(&data),
+            SignatureType::X509 => None,
+        });
+        Some(Self {
+            signature_type,
+            signer_key,
+        })
+    }
+}
+
+/// Decodes the base64 data of an ASCII armor.
+///
+/// The armor headers and the checksum line are skipped.
+fn decode_armor<'a>(lines: impl Iterator<Item = &'a str>) -> Option<Vec<u8>> {
+    let lines = lines
+        .take_while(|line| !line.starts_with("-----END "))
+        .collect::<Vec<&str>>();
+    let body = match lines.iter().position(|line| line.is_empty()) {
+        Some(index) => &lines[index + 1..],
+        None => &lines[..],
+    };
+    let data = body
+        .iter()
+        .filter(|line| !line.starts_with('='))
+        .copied()
+        .collect::<String>();
+    BASE64.decode(data).ok()
+}
+
+/// Returns the issuer fingerprint or key ID of an OpenPGP signature packet.
+///
+/// <https://www.rfc-editor.org/rfc/rfc9580#name-signature-packet-type-id-2>
+fn pgp_issuer(data: &[u8]) -> Option<String> {
+    let tag = *data.first()?;
+    if tag & 0x80 == 0 {
+        return None;
+    }
+    let (packet_type, offset, length): (u8, usize, usize) = if tag & 0x40 == 0 {
+        // Legacy packet format.
+        let (offset, length) = match tag & 0x03 {
+            0 => (2, usize::from(*data.get(1)?)),
+            1 => (3, usize::from(read_u16(data.get(1..)?)?)),
+            2 => (5, read_u32(data.get(1..)?)? as usize),
+            _ => (1, data.len() - 1),
+        };
+        ((tag >> 2) & 0x0f, offset, length)
+    } else {
+        let (offset, length) = match *data.get(1)? {
+            octet @ 0..=191 => (2, usize::from(octet)),
+            octet @ 192..=223 => (
+                3,
+                ((usize::from(octet) - 192) << 8) + usize::from(*data.get(2)?) + 192,
+            ),
+            255 => (6, read_u32(data.get(2..)?)? as usize),
+            _ => return None,
+        };
+        (tag & 0x3f, offset, length)
+    };
+    if packet_type != PGP_SIGNATURE_PACKET {
+        return None;
+    }
+    let body = data.get(offset..offset.checked_add(length)?)?;
+    match *body.first()? {
+        // Version 3 signatures contain the key ID at a fixed position.
+        3 => Some(to_hex(body.get(7..15)?)),
+        version @ 4..=6 => {
+            let length_size: usize = if version == 6 { 4 } else { 2 };
+            let read_length = |data: &[u8]| -> Option<usize> {
+                if length_size == 4 {
+                    read_u32(data).map(|v| v as usize)
+                } else {
+                    read_u16(data).map(usize::from)
+                }
+            };
+            let hashed_length = read_length(body.get(4..)?)?;
+            let hashed_start = 4 + length_size;
+            let unhashed_start = hashed_start.checked_add(hashed_length)?;
+            let hashed = body.get(hashed_start..unhashed_start)?;
+            let unhashed_length = read_length(body.get(unhashed_start..)?)?;
+            let unhashed = body.get(
+                unhashed_start + length_size..
+                    (unhashed_start + length_size).checked_add(unhashed_length)?,
+            )?;
+            let subpackets = pgp_subpackets(hashed)
+                .into_iter()
+                .chain(pgp_subpackets(unhashed))
+                .collect::<Vec<(u8, &[u8])>>();
+            subpackets
+                .iter()
+                .find(|(kind, value)| *kind == PGP_ISSUER_FINGERPRINT_SUBPACKET && value.len() > 1)
+                .map(|(_, value)| to_hex(&value[1..]))
+                .or_else(|| {
+                    subpackets
+                        .iter()
+                        .find(|(kind, _)| *kind == PGP_ISSUER_SUBPACKET)
+                        .map(|(_, value)| to_hex(value))
+                })
+        }
+        _ => None,
+    }
+}
+
+/// Splits the given data into OpenPGP signature subpackets.
+///
+/// Returns the type and the value of each subpacket.
+fn pgp_subpackets(mut data: &[u8]) -> Vec<(u8, &[u8])> {
+    let mut subpackets = Vec::new();
+    while let Some(&octet) = data.first() {
+        let (offset, length): (usize, usize) = match octet {
+            0..=191 => (1, usize::from(octet)),
+            192..=254 => match data.get(1) {
+                Some(next) => (
+                    2,
+                    ((usize::from(octet) - 192) << 8) + usize::from(*next) + 192,
+                ),
+                None => break,
+            },
+            255 => match data.get(1..).and_then(read_u32) {
+                Some(length) => (5, length as usize),
+                None => break,
+            },
+        };
+        let Some((subpacket, rest)) = offset
+            .checked_add(length)
+            .and_then(|end| Some((data.get(offset..end)?, data.get(end..)?)))
+        else {
+            break;
+        };
+        if let Some((kind, value)) = subpacket.split_first() {
+            subpackets.push((kind & 0x7f, value));
+        }
+        data = rest;
+    }
+    subpackets
+}
+
+/// Returns the public key of an SSH signature in `<type> <base64>` format.
+fn ssh_public_key(data: &[u8]) -> Option<String> {
+    let data = data.strip_prefix(SSH_SIGNATURE_MAGIC)?;
+    // Skip the signature version.
+    let (public_key, _) = read_ssh_string(data.get(4..)?)?;
+    let (key_type, _) = read_ssh_string(public_key)?;
+    Some(format!(
+        "{} {}",
+        String::from_utf8_lossy(key_type),
+        BASE64.encode(public_key)
+    ))
+}
+
+/// Reads a length-prefixed string in the SSH wire format.
+///
+/// Returns the string and the remaining data.
+fn read_ssh_string(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let length = read_u32(data)? as usize;
+    let end = 4usize.checked_add(length)?;
+    Some((data.get(4..end)?, data.get(end..)?))
+}
+
+/// Reads a big-endian `u16` from the start of the given data.
+fn read_u16(data: &[u8]) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(..2)?.try_into().ok()?))
+}
+
+/// Reads a big-endian `u32` from the start of the given data.
+fn read_u32(data: &[u8]) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(..4)?.try_into().ok()?))
+}
+
+/// Formats the given bytes as uppercase hexadecimal.
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{byte:02X}")).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const GPG_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----
+
+iHUEABYIAB0WIQT7JDEb1JprWi2ZfsThpGcdjggwTQUCatVK9gAKCRDhpGcdjggw
+TeP7AQC4V3WqGxPXgE8c3w1gyKiuyd0Agq1dx8n2HUXslbAomAEAyeNOWRfaG9Eo
+V5GZK3ElG2kfa7nh6sASOfDvZcRZWgw=
+=7LZb
+-----END PGP SIGNATURE-----";
+
+    const SSH_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAg2o5fThBkxAdzlVNRgYGYmqEIfm
+GL8VKSj9xCvmT/00AAAAADZ2l0AAAAAAAAAAZzaGE1MTIAAABTAAAAC3NzaC1lZDI1NTE5
+AAAAQJ/WhXgmq1sbMCcYoX3il3Hhpl3noLWHncfo2VG+OGsf3TCMdblduXZ7EwltiA9NoF
+IIYSJDBRmdzltkbVxtuQg=
+-----END SSH SIGNATURE-----";
+
+    #[test]
+    fn parse_gpg_signature() {
+        assert_eq!(
+            Some(SignatureInfo {
+                signature_type: SignatureType::Gpg,
+                signer_key: Some(String::from("FB24311BD49A6B5A2D997EC4E1A4671D8E08304D")),
+            }),
+            SignatureInfo::parse(GPG_SIGNATURE)
+        );
+    }
+
+    #[test]
+    fn parse_ssh_signature() {
+        assert_eq!(
+            Some(SignatureInfo {
+                signature_type: SignatureType::Ssh,
+                signer_key: Some(String::from(
+                    "ssh-ed25519 \
+                     AAAAC3NzaC1lZDI1NTE5AAAAINqOX04QZMQHc5VTUYGBmJqhCH5hi/FSko/cQr5k/9NA"
+                )),
+            }),
+            SignatureInfo::parse(SSH_SIGNATURE)
+        );
+    }
+
+    #[test]
+    fn parse_unknown_signature() {
+        assert_eq!(
+            Some(SignatureInfo {
+                signature_type: SignatureType::X509,
+                signer_key: None,
+            }),
+            SignatureInfo::parse("-----BEGIN SIGNED MESSAGE-----\n-----END SIGNED MESSAGE-----")
+        );
+        assert_eq!(
+            Some(SignatureInfo {
+                signature_type: SignatureType::Gpg,
+                signer_key: None,
+            }),
+            SignatureInfo::parse(
+                "-----BEGIN PGP SIGNATURE-----\n\ninvalid\n-----END PGP SIGNATURE-----"
+            )
+        );
+        assert_eq!(None, SignatureInfo::parse("not a signature"));
+    }
+
+    #[test]
+    fn parse_truncated_signature() {
+        for signature in [GPG_SIGNATURE, SSH_SIGNATURE] {
+            let info = SignatureInfo::parse(signature).expect("valid signature");
+            for end in 0..signature.len() {
+                if let Some(key) =
+                    SignatureInfo::parse(&signature[..end]).and_then(|info| info.signer_key)
+                {
+                    assert_eq!(info.signer_key.as_ref(), Some(&key));
+                }
+            }
+            // Truncated data either misses the key or contains the whole key.
+            let data = decode_armor(signature.lines().skip(1)).expect("valid armor");
+            for end in 0..data.len() {
+                for parse in [pgp_issuer, ssh_public_key] {
+                    if let Some(key) = parse(&data[..end]) {
+                        assert_eq!(parse(&data), Some(key));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_garbage_signature() {
+        // Packets and subpackets with lengths that exceed the data.
+        for data in [
+            &[0x88, 0xff][..],
+            &[0x8a, 0xff, 0xff, 0xff, 0xff, 0x04],
+            &[0xc2, 0xff, 0xff, 0xff, 0xff, 0xff, 0x04],
+            &[0xc2, 0x08, 0x04, 0x00, 0x16, 0x08, 0xff, 0xff, 0x00, 0x00],
+            &[
+                0xc2, 0x0c, 0x06, 0x00, 0x16, 0x08, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00,
+            ],
+            &[0xc2, 0x08, 0x04, 0x00, 0x16, 0x08, 0x00, 0x02, 0xff, 0xff],
+            &[0xc2, 0x07, 0x04, 0x00, 0x16, 0x08, 0x00, 0x01, 0xfe],
+        ] {
+            assert_eq!(None, pgp_issuer(data));
+        }
+        for data in [
+            &b"SSHSIG"[..],
+            b"SSHSIG\x00\x00\x00\x01\xff\xff\xff\xff",
+            b"SSHSIG\x00\x00\x00\x01\x00\x00\x00\x08\xff\xff\xff\xff",
+        ] {
+            assert_eq!(None, ssh_public_key(data));
+        }
+
+        // Pseudo-random data.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..1000 {
+            let data = (0..64)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state.to_be_bytes()[0]
+                })
+                .collect::<Vec<u8>>();
+            for start in [0, 1, 2, 6] {
+                pgp_issuer(&data[start..]);
+                ssh_public_key(&data[start..]);
+                pgp_subpackets(&data[start..]);
+            }
+            for header in [
+                "-----BEGIN PGP SIGNATURE-----",
+                "-----BEGIN SSH SIGNATURE-----",
+            ] {
+                assert!(
+                    SignatureInfo::parse(&format!("{header}\n\n{}\n", BASE64.encode(&data)))
+                        .is_some()
+                );
+            }
+        }
+    }
+}
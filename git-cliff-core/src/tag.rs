@@ -1,12 +1,34 @@
+use crate::commit::Signature;
+use crate::signature::{SignatureInfo, SignatureType};
+
 /// Common tag object that is parsed from a repository.
 ///
 /// Lightweight tags will have `None` as message.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Tag {
     /// The name of the tag
     pub name: String,
     /// The message of the tag (only if it was annotated).
     pub message: Option<String>,
+    /// Whether if the tag is signed (only if it was annotated).
+    pub signed: bool,
+    /// Type of the tag signature.
+    pub signature_type: Option<SignatureType>,
+    /// Key that is used for signing the tag.
+    pub signer_key: Option<String>,
+    /// Tagger of the tag (only if it was annotated).
+    pub tagger: Option<Signature>,
+    /// Time of the tag (only if it was annotated).
+    pub timestamp: Option<i64>,
+}
+
+impl Tag {
+    /// Sets the signature information of the tag.
+    pub fn set_signature(&mut self, signature: Option<SignatureInfo>) {
+        self.signed = signature.is_some();
+        self.signature_type = signature.as_ref().map(|v| v.signature_type);
+        self.signer_key = signature.and_then(|v| v.signer_key);
+    }
 }
 
 #[cfg(test)]
@@ -18,6 +40,7 @@ mod test {
         let tag = Tag {
             name: String::from("v1.0"),
             message: Some(String::from("Initial release")),
+            ..Default::default()
         };
         assert_eq!(tag.name, "v1.0");
         assert_eq!(tag.message, Some(String::from("Initial release")));
@@ -28,20 +51,38 @@ mod test {
         let tag = Tag {
             name: String::from("v1.0"),
             message: None,
+            ..Default::default()
         };
         assert_eq!(tag.name, "v1.0");
         assert_eq!(tag.message, None);
     }
 
+    #[test]
+    fn create_signed_tag() {
+        let mut tag = Tag {
+            name: String::from("v1.0"),
+            ..Default::default()
+        };
+        tag.set_signature(Some(SignatureInfo {
+            signature_type: SignatureType::Ssh,
+            signer_key: Some(String::from("ssh-ed25519 AAAA")),
+        }));
+        assert!(tag.signed);
+        assert_eq!(tag.signature_type, Some(SignatureType::Ssh));
+        assert_eq!(tag.signer_key, Some(String::from("ssh-ed25519 AAAA")));
+    }
+
     #[test]
     fn debug_print_tag_with_message() {
         let tag = Tag {
             name: String::from("v1.0"),
             message: Some(String::from("Initial release")),
+            ..Default::default()
         };
         assert_eq!(
             format!("{tag:?}"),
-            "Tag { name: \"v1.0\", message: Some(\"Initial release\") }"
+            "Tag { name: \"v1.0\", message: Some(\"Initial release\"), signed: false, \
+             signature_type: None, signer_key: None, tagger: None, timestamp: None }"
         );
     }
 }
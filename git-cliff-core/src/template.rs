@@ -1,15 +1,16 @@
 use std::collections::{HashMap, HashSet};
 use std::error::Error as ErrorImpl;
+use std::sync::Arc;
 
 use indexmap::IndexMap;
 use regex::Regex;
-use semver::Version;
 use serde::Serialize;
 use serde_json::{Map, json};
 use tera::{Context as TeraContext, Result as TeraResult, Tera, Value, ast};
 
 use crate::config::TextProcessor;
 use crate::error::{Error, Result};
+use crate::version::{ParsedVersion, Semver, VersionScheme};
 
 /// Wrapper for [`Tera`].
 #[derive(Debug)]
@@ -47,13 +48,22 @@ impl Template {
         tera.register_filter("replace_regex", Self::replace_regex);
         tera.register_filter("find_regex", Self::find_regex);
         tera.register_filter("commit_groups", Self::commit_groups);
-        tera.register_filter("group_by_scope", Self::group_by_scope);
 
-        Ok(Self {
+        let mut template = Self {
             name: name.to_string(),
             variables: Self::get_template_variables(name, &tera)?,
             tera,
-        })
+        };
+        template.set_version_scheme(Arc::new(Semver));
+        Ok(template)
+    }
+
+    /// Sets the versioning scheme that is used by the `group_by_scope` filter.
+    pub fn set_version_scheme(&mut self, scheme: Arc<dyn VersionScheme>) {
+        self.tera
+            .register_filter("group_by_scope", move |value: &Value, args: &_| {
+                Self::group_by_scope(scheme.as_ref(), value, args)
+            });
     }
 
     /// Groups commits by their `group` field while preserving ordering.
@@ -204,8 +214,12 @@ impl Template {
         Ok(tera::to_value(result)?)
     }
 
-    /// Groups releases by the semantic version scope of their `version` field.
-    fn group_by_scope(value: &Value, args: &HashMap<String, Value>) -> TeraResult<Value> {
+    /// Groups releases by the version scope of their `version` field.
+    fn group_by_scope(
+        scheme: &dyn VersionScheme,
+        value: &Value,
+        args: &HashMap<String, Value>,
+    ) -> TeraResult<Value> {
         let releases = tera::try_get_value!("group_by_scope", "value", Vec<Value>, value);
         if releases.is_empty() {
             return Ok(Map::new().into());
@@ -225,7 +239,7 @@ impl Template {
                 Some(version) => version.to_string(),
                 None => continue,
             };
-            let key = scoped_version(&key, &prefix, scope).unwrap_or(key);
+            let key = scoped_version(scheme, &key, &prefix, scope).unwrap_or(key);
 
             let releases = grouped
                 .entry(key)
@@ -378,21 +392,34 @@ impl VersionScope {
     }
 }
 
-fn scoped_version(version: &str, prefix: &str, scope: VersionScope) -> Option<String> {
+fn scoped_version(
+    scheme: &dyn VersionScheme,
+    version: &str,
+    prefix: &str,
+    scope: VersionScope,
+) -> Option<String> {
     let version = version.strip_prefix(prefix)?;
-    let version = Version::parse(version).ok()?;
+    let version = scheme.parse(version).filter(|v| v.prefix.is_empty())?;
     Some(format_scoped_version(prefix, &version, scope))
 }
 
-fn format_scoped_version(prefix: &str, version: &Version, scope: VersionScope) -> String {
-    match scope {
-        VersionScope::Major => format!("{prefix}{}", version.major),
-        VersionScope::Minor => format!("{prefix}{}.{}", version.major, version.minor),
-        VersionScope::Patch => format!(
-            "{prefix}{}.{}.{}",
-            version.major, version.minor, version.patch
-        ),
-    }
+fn format_scoped_version(prefix: &str, version: &ParsedVersion, scope: VersionScope) -> String {
+    let length = match scope {
+        VersionScope::Major => 1,
+        VersionScope::Minor => 2,
+        VersionScope::Patch => 3,
+    };
+    let components = (0..length)
+        .map(|i| {
+            version
+                .release
+                .get(i)
+                .copied()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect::<Vec<String>>();
+    format!("{prefix}{}", components.join("."))
 }
 
 #[cfg(test)]
@@ -438,6 +465,14 @@ mod test {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: crate::remote::RemoteReleaseMetadata {
                 contributors: vec![],
@@ -685,4 +720,25 @@ mod test {
         assert_eq!("=1:1:chore=1,;v0.9=1:1:docs=1,;v1.0=2:3:feat=1,fix=2,;", r);
         Ok(())
     }
+
+    #[test]
+    fn test_group_by_scope_filter_with_version_scheme() -> Result<()> {
+        let releases = vec![
+            release_with_commits(Some("1.2.0.post1"), &["fix: fix endpoint"]),
+            release_with_commits(Some("1.2.0"), &["feat: add endpoint"]),
+            release_with_commits(Some("1.2.0a3"), &["feat: add button"]),
+            release_with_commits(Some("1.1"), &["docs: update docs"]),
+        ];
+        let mut context = HashMap::new();
+        context.insert("releases", releases);
+        let template = r#"{% for version, releases in releases | group_by_scope(scope="minor") %}{{ version }}={{ releases | length }};{% endfor %}"#;
+        let mut template = Template::new("test", template.to_string(), true)?;
+        let r = template.render(&get_fake_release_data(), Some(&context), &[])?;
+        assert_eq!("1.1=1;1.2=1;1.2.0.post1=1;1.2.0a3=1;", r);
+
+        template.set_version_scheme(Arc::new(crate::version::Pep440));
+        let r = template.render(&get_fake_release_data(), Some(&context), &[])?;
+        assert_eq!("1.1=1;1.2=3;", r);
+        Ok(())
+    }
 }
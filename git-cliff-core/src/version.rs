@@ -0,0 +1,1529 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
+use next_version::{NextVersion as NextVersionTrait, VersionUpdater};
+use regex::Regex;
+use semver::{BuildMetadata, Prerelease, Version};
+
+use crate::calver::CalverFormat;
+use crate::commit::Commit;
+use crate::config::{Bump, BumpLevel, BumpType};
+use crate::error::{Error, Result};
+use crate::release::{BumpReason, NextVersion, Release};
+use crate::tag::Tag;
+
+/// Default identifier of the pre-release versions.
+const DEFAULT_PRERELEASE_IDENTIFIER: &str = "rc";
+
+/// Pre-release identifier of the Maven snapshot versions.
+const SNAPSHOT_IDENTIFIER: &str = "SNAPSHOT";
+
+/// Minimum bump level of the releases with dependency updates.
+const DEPENDENCY_BUMP_LEVEL: BumpLevel = BumpLevel::Patch;
+
+/// Regex for matching the semantic versions with less than three components,
+/// e.g. Maven-style `1.2-SNAPSHOT`.
+static SHORT_SEMVER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d+)\.(\d+)(?:\.(\d+))?(?:-([0-9A-Za-z.-]+))?(?:\+([0-9A-Za-z.-]+))?$")
+        .expect("valid short semver regex")
+});
+
+/// Regex for matching the PEP 440 versions.
+///
+/// <https://packaging.python.org/en/latest/specifications/version-specifiers/#appendix-parsing-version-strings-with-regular-expressions>
+static PEP440_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?ix)^
+        (?:(?P<epoch>\d+)!)?
+        (?P<release>\d+(?:\.\d+)*)
+        (?:[-_.]?(?P<pre_l>alpha|a|beta|b|preview|pre|c|rc)[-_.]?(?P<pre_n>\d+)?)?
+        (?:-(?P<post_n1>\d+)|[-_.]?(?P<post_l>post|rev|r)[-_.]?(?P<post_n2>\d+)?)?
+        (?:[-_.]?(?P<dev>dev)[-_.]?(?P<dev_n>\d+)?)?
+        (?:\+(?P<local>[a-z0-9]+(?:[-_.][a-z0-9]+)*))?
+        $",
+    )
+    .expect("valid PEP 440 regex")
+});
+
+/// Regex for matching the release lines, e.g. `1.x` or `release/1.2.x`.
+static RELEASE_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^0-9])(\d+)(?:\.(\d+))?\.[xX]$").expect("valid release line regex")
+});
+
+/// Version that is parsed by a [`VersionScheme`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedVersion {
+    /// Prefix of the version, e.g. `v` or `app-v`.
+    pub prefix: String,
+    /// Epoch of the version (PEP 440).
+    pub epoch: Option<u64>,
+    /// Numeric components of the version, e.g. `[1, 2, 0]`.
+    pub release: Vec<u64>,
+    /// Pre-release identifier, e.g. `rc.1`, `a3` or `SNAPSHOT`.
+    pub pre: Option<String>,
+    /// Post-release number (PEP 440).
+    pub post: Option<u64>,
+    /// Development release number (PEP 440).
+    pub dev: Option<u64>,
+    /// Build metadata (semver) or local version label (PEP 440).
+    pub build: Option<String>,
+}
+
+/// Versioning scheme.
+///
+/// It is used for bumping the version, sorting the tags and grouping the
+/// releases in templates.
+pub trait VersionScheme: Send + Sync {
+    /// Parses the given version.
+    ///
+    /// The non-numeric prefix of the version (e.g. `v`) is preserved.
+    ///
+    /// Returns `None` if the version is not valid in this scheme.
+    fn parse(&self, version: &str) -> Option<ParsedVersion>;
+
+    /// Compares the precedence of the given versions.
+    ///
+    /// The prefixes of the versions are ignored.
+    fn compare(&self, a: &ParsedVersion, b: &ParsedVersion) -> Ordering;
+
+    /// Calculates the next version of the given release.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the previous version is not valid or the bump
+    /// configuration is invalid.
+    fn bump(&self, release: &Release, config: &Bump) -> Result<NextVersion>;
+
+    /// Formats the given version.
+    fn format(&self, version: &ParsedVersion) -> String;
+
+    /// Compares the precedence of the given version strings.
+    ///
+    /// Versions that cannot be parsed have lower precedence than the valid
+    /// versions.
+    fn compare_str(&self, a: &str, b: &str) -> Ordering {
+        match (self.parse(a), self.parse(b)) {
+            (Some(a), Some(b)) => self.compare(&a, &b),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// [Semantic versioning](https://semver.org) scheme.
+///
+/// Versions with less than three components (e.g. Maven-style `1.2-SNAPSHOT`)
+/// are also supported.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Semver;
+
+impl Semver {
+    /// Parses a version without the prefix.
+    fn parse_version(version: &str) -> Option<ParsedVersion> {
+        let version = Version::parse(version).ok()?;
+        Some(ParsedVersion {
+            release: vec![version.major, version.minor, version.patch],
+            pre: (!version.pre.is_empty()).then(|| version.pre.to_string()),
+            build: (!version.build.is_empty()).then(|| version.build.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Parses a version with less than three components without the prefix.
+    fn parse_short_version(version: &str) -> Option<ParsedVersion> {
+        let captures = SHORT_SEMVER_REGEX.captures(version)?;
+        Some(ParsedVersion {
+            release: captures
+                .iter()
+                .skip(1)
+                .take(3)
+                .flatten()
+                .map(|v| v.as_str().parse().ok())
+                .collect::<Option<Vec<u64>>>()?,
+            pre: captures.get(4).map(|v| v.as_str().to_string()),
+            build: captures.get(5).map(|v| v.as_str().to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Converts the parsed version to a semantic version.
+    fn to_semver(version: &ParsedVersion) -> Result<Version> {
+        let release = padded_release(version);
+        Ok(Version {
+            major: release[0],
+            minor: release[1],
+            patch: release[2],
+            pre: Prerelease::new(version.pre.as_deref().unwrap_or_default())?,
+            build: BuildMetadata::new(version.build.as_deref().unwrap_or_default())?,
+        })
+    }
+}
+
+impl VersionScheme for Semver {
+    fn parse(&self, version: &str) -> Option<ParsedVersion> {
+        parse_with_prefix(version, Self::parse_version)
+            .or_else(|| parse_with_prefix(version, Self::parse_short_version))
+    }
+
+    fn compare(&self, a: &ParsedVersion, b: &ParsedVersion) -> Ordering {
+        padded_release(a)
+            .cmp(&padded_release(b))
+            .then_with(|| match (&a.pre, &b.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_identifiers(a, b),
+            })
+    }
+
+    fn bump(&self, release: &Release, config: &Bump) -> Result<NextVersion> {
+        let Some(version) = previous_version(release, config) else {
+            return Ok(NextVersion {
+                previous_version: None,
+                version: config.get_initial_tag(),
+                bump_type: None,
+                commits: Vec::new(),
+            });
+        };
+        let current = self
+            .parse(&version)
+            .ok_or_else(|| invalid_version(&version))?;
+        let old_semver = Self::to_semver(&current)?;
+        let mut commits = Vec::new();
+        let (new_semver, bump_type) = match config.bump_type {
+            Some(BumpType::Prerelease) => {
+                let new_semver =
+                    next_prerelease(&old_semver, config.prerelease_identifier.as_deref(), || {
+                        increment(&old_semver, release, config).map(|(version, _)| version)
+                    })?;
+                (new_semver, Some(BumpType::Prerelease))
+            }
+            Some(BumpType::Major) => (old_semver.increment_major(), Some(BumpType::Major)),
+            Some(BumpType::Minor) => (old_semver.increment_minor(), Some(BumpType::Minor)),
+            Some(BumpType::Patch) => (old_semver.increment_patch(), Some(BumpType::Patch)),
+            // Promote the pre-release (or the snapshot) to a normal version.
+            Some(BumpType::Release) if !old_semver.pre.is_empty() => {
+                let new_semver = Version {
+                    pre: Prerelease::EMPTY,
+                    ..old_semver.clone()
+                };
+                (new_semver, Some(BumpType::Release))
+            }
+            None if old_semver.pre.as_str() == SNAPSHOT_IDENTIFIER => {
+                let new_semver = Version {
+                    pre: Prerelease::EMPTY,
+                    ..old_semver.clone()
+                };
+                (new_semver, None)
+            }
+            Some(BumpType::Release) | None => {
+                let (new_semver, reasons) = increment(&old_semver, release, config)?;
+                commits = reasons;
+                let bump_type = determine_bump_type(&old_semver, &new_semver);
+                (new_semver, bump_type)
+            }
+        };
+        Ok(NextVersion {
+            previous_version: Some(version),
+            version: self.format(&ParsedVersion {
+                prefix: current.prefix,
+                release: release_from_semver(&new_semver, current.release.len()),
+                pre: (!new_semver.pre.is_empty()).then(|| new_semver.pre.to_string()),
+                build: (!new_semver.build.is_empty()).then(|| new_semver.build.to_string()),
+                ..Default::default()
+            }),
+            bump_type,
+            commits,
+        })
+    }
+
+    fn format(&self, version: &ParsedVersion) -> String {
+        let mut output = format!("{}{}", version.prefix, join_release(&version.release));
+        if let Some(pre) = &version.pre {
+            output.push_str(&format!("-{pre}"));
+        }
+        if let Some(build) = &version.build {
+            output.push_str(&format!("+{build}"));
+        }
+        output
+    }
+}
+
+/// [PEP 440](https://peps.python.org/pep-0440/) versioning scheme.
+///
+/// Pre-release identifiers are normalized to `a`, `b` and `rc`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pep440;
+
+impl Pep440 {
+    /// Parses a version without the prefix.
+    fn parse_version(version: &str) -> Option<ParsedVersion> {
+        let captures = PEP440_REGEX.captures(version)?;
+        let number = |name: &str| -> Option<u64> { captures.name(name)?.as_str().parse().ok() };
+        let pre = captures.name("pre_l").map(|label| {
+            format!(
+                "{}{}",
+                normalize_pep440_label(label.as_str()),
+                number("pre_n").unwrap_or_default()
+            )
+        });
+        let post = number("post_n1").or_else(|| {
+            captures
+                .name("post_l")
+                .map(|_| number("post_n2").unwrap_or_default())
+        });
+        Some(ParsedVersion {
+            epoch: number("epoch"),
+            release: captures
+                .name("release")?
+                .as_str()
+                .split('.')
+                .map(|v| v.parse().ok())
+                .collect::<Option<Vec<u64>>>()?,
+            pre,
+            post,
+            dev: captures
+                .name("dev")
+                .map(|_| number("dev_n").unwrap_or_default()),
+            build: captures
+                .name("local")
+                .map(|v| v.as_str().to_lowercase().replace(['-', '_'], ".")),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the sort key of the given version.
+    ///
+    /// <https://packaging.python.org/en/latest/specifications/version-specifiers/#summary-of-permitted-suffixes-and-relative-ordering>
+    #[allow(clippy::type_complexity)]
+    fn sort_key(
+        version: &ParsedVersion,
+    ) -> (
+        u64,
+        Vec<u64>,
+        (u8, u64),
+        (u8, u64),
+        (u8, u64),
+        Option<Vec<(u8, u64, String)>>,
+    ) {
+        let mut release = version.release.clone();
+        while release.len() > 1 && release.last() == Some(&0) {
+            release.pop();
+        }
+        let pre = match &version.pre {
+            Some(pre) => {
+                let (label, number) = split_pep440_pre(pre);
+                let rank = match label {
+                    "a" => 1,
+                    "b" => 2,
+                    _ => 3,
+                };
+                (rank, number)
+            }
+            // Development releases without a pre-release come before the
+            // pre-releases.
+            None if version.post.is_none() && version.dev.is_some() => (0, 0),
+            None => (4, 0),
+        };
+        let post = version.post.map_or((0, 0), |post| (1, post));
+        let dev = version.dev.map_or((1, 0), |dev| (0, dev));
+        let local = version.build.as_ref().map(|local| {
+            local
+                .split('.')
+                .map(|segment| match segment.parse::<u64>() {
+                    Ok(number) => (1, number, String::new()),
+                    Err(_) => (0, 0, segment.to_string()),
+                })
+                .collect()
+        });
+        (
+            version.epoch.unwrap_or_default(),
+            release,
+            pre,
+            post,
+            dev,
+            local,
+        )
+    }
+}
+
+impl VersionScheme for Pep440 {
+    fn parse(&self, version: &str) -> Option<ParsedVersion> {
+        parse_with_prefix(version, Self::parse_version)
+    }
+
+    fn compare(&self, a: &ParsedVersion, b: &ParsedVersion) -> Ordering {
+        Self::sort_key(a).cmp(&Self::sort_key(b))
+    }
+
+    fn bump(&self, release: &Release, config: &Bump) -> Result<NextVersion> {
+        let Some(version) = previous_version(release, config) else {
+            return Ok(NextVersion {
+                previous_version: None,
+                version: config.get_initial_tag(),
+                bump_type: None,
+                commits: Vec::new(),
+            });
+        };
+        let current = self
+            .parse(&version)
+            .ok_or_else(|| invalid_version(&version))?;
+        let padded = padded_release(&current);
+        let base = Version::new(padded[0], padded[1], padded[2]);
+        let identifier = config
+            .prerelease_identifier
+            .as_deref()
+            .map(normalize_pep440_label);
+        let current_pre = current.pre.as_deref().map(split_pep440_pre);
+        let normal = |release: Vec<u64>| ParsedVersion {
+            prefix: current.prefix.clone(),
+            epoch: current.epoch,
+            release,
+            ..Default::default()
+        };
+        let mut commits = Vec::new();
+        let (next, bump_type) = match config.bump_type {
+            Some(BumpType::Prerelease) => {
+                let identifier = identifier
+                    .or(current_pre.map(|(label, _)| label))
+                    .unwrap_or(DEFAULT_PRERELEASE_IDENTIFIER);
+                let next = match current_pre {
+                    Some((label, number)) if label == identifier => ParsedVersion {
+                        pre: Some(format!("{label}{}", number + 1)),
+                        ..normal(current.release.clone())
+                    },
+                    Some(_) => ParsedVersion {
+                        pre: Some(format!("{identifier}1")),
+                        ..normal(current.release.clone())
+                    },
+                    None => {
+                        let (mut next_base, _) = increment(&base, release, config)?;
+                        if next_base == base {
+                            next_base = base.increment_patch();
+                        }
+                        ParsedVersion {
+                            pre: Some(format!("{identifier}1")),
+                            ..normal(release_from_semver(&next_base, current.release.len()))
+                        }
+                    }
+                };
+                (next, Some(BumpType::Prerelease))
+            }
+            Some(bump_type @ (BumpType::Major | BumpType::Minor | BumpType::Patch)) => {
+                let next_base = match bump_type {
+                    BumpType::Major => base.increment_major(),
+                    BumpType::Minor => base.increment_minor(),
+                    _ => base.increment_patch(),
+                };
+                (
+                    normal(release_from_semver(&next_base, current.release.len())),
+                    Some(bump_type),
+                )
+            }
+            // Promote the pre-release (or the development release) to a
+            // normal version.
+            Some(BumpType::Release) if current.pre.is_some() || current.dev.is_some() => {
+                (normal(current.release.clone()), Some(BumpType::Release))
+            }
+            // Increment the pre-release number.
+            None if current_pre.is_some() => {
+                let (label, number) = current_pre.unwrap_or_default();
+                let next = ParsedVersion {
+                    pre: Some(format!("{label}{}", number + 1)),
+                    ..normal(current.release.clone())
+                };
+                (next, None)
+            }
+            // Promote the development release to a normal version.
+            None if current.dev.is_some() => (normal(current.release.clone()), None),
+            Some(BumpType::Release) | None => {
+                let (next_base, reasons) = increment(&base, release, config)?;
+                match determine_bump_type(&base, &next_base) {
+                    Some(bump_type) => {
+                        commits = reasons;
+                        (
+                            normal(release_from_semver(&next_base, current.release.len())),
+                            Some(bump_type),
+                        )
+                    }
+                    None => (current, None),
+                }
+            }
+        };
+        Ok(NextVersion {
+            previous_version: Some(version),
+            version: self.format(&next),
+            bump_type,
+            commits,
+        })
+    }
+
+    fn format(&self, version: &ParsedVersion) -> String {
+        let mut output = version.prefix.clone();
+        if let Some(epoch) = version.epoch {
+            output.push_str(&format!("{epoch}!"));
+        }
+        output.push_str(&join_release(&version.release));
+        if let Some(pre) = &version.pre {
+            output.push_str(pre);
+        }
+        if let Some(post) = version.post {
+            output.push_str(&format!(".post{post}"));
+        }
+        if let Some(dev) = version.dev {
+            output.push_str(&format!(".dev{dev}"));
+        }
+        if let Some(local) = &version.build {
+            output.push_str(&format!("+{local}"));
+        }
+        output
+    }
+}
+
+/// [Calendar versioning](https://calver.org) scheme.
+#[derive(Debug, Clone)]
+pub struct Calver {
+    /// Format of the versions.
+    format: CalverFormat,
+}
+
+impl Calver {
+    /// Constructs a new instance with the given format.
+    #[must_use]
+    pub fn new(format: CalverFormat) -> Self {
+        Self { format }
+    }
+}
+
+impl VersionScheme for Calver {
+    fn parse(&self, version: &str) -> Option<ParsedVersion> {
+        let (prefix, release) = self.format.parse_version(version)?;
+        Some(ParsedVersion {
+            prefix,
+            release,
+            ..Default::default()
+        })
+    }
+
+    fn compare(&self, a: &ParsedVersion, b: &ParsedVersion) -> Ordering {
+        a.release.cmp(&b.release)
+    }
+
+    fn bump(&self, release: &Release, config: &Bump) -> Result<NextVersion> {
+        let previous = previous_version(release, config);
+        if previous.is_none() {
+            if let Some(initial_tag) = &config.initial_tag {
+                return Ok(NextVersion {
+                    previous_version: None,
+                    version: initial_tag.clone(),
+                    bump_type: None,
+                    commits: Vec::new(),
+                });
+            }
+        }
+        // Use the date of the latest commit, or the current date if there are no
+        // commits.
+        let date = release
+            .commits
+            .iter()
+            .map(|commit| commit.committer.timestamp)
+            .filter(|timestamp| *timestamp > 0)
+            .max()
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+            .unwrap_or_else(Utc::now);
+        Ok(NextVersion {
+            version: self.format.next_version(previous.as_deref(), &date),
+            previous_version: previous,
+            bump_type: None,
+            commits: Vec::new(),
+        })
+    }
+
+    fn format(&self, version: &ParsedVersion) -> String {
+        self.format
+            .format_version(&version.prefix, &version.release)
+    }
+}
+
+/// Line of the versions that share the same major (and minor) version, e.g.
+/// `1.x` or `1.2.x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReleaseLine {
+    /// Major version of the line.
+    pub major: u64,
+    /// Minor version of the line.
+    pub minor: Option<u64>,
+}
+
+impl ReleaseLine {
+    /// Parses the release line from the given value, e.g. `1.x`, `1.2.x` or
+    /// a branch name such as `release/1.x`.
+    ///
+    /// Returns `None` if the value does not end with a release line.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        let captures = RELEASE_LINE_REGEX.captures(value)?;
+        Some(Self {
+            major: captures.get(1)?.as_str().parse().ok()?,
+            minor: captures.get(2).and_then(|v| v.as_str().parse().ok()),
+        })
+    }
+
+    /// Returns whether the given version belongs to the release line.
+    #[must_use]
+    pub fn contains(&self, version: &ParsedVersion) -> bool {
+        version.release.first() == Some(&self.major) &&
+            self.minor
+                .is_none_or(|minor| version.release.get(1).copied().unwrap_or_default() == minor)
+    }
+
+    /// Returns whether the given version precedes the release line, e.g.
+    /// `0.9.0` for the `1.x` line.
+    #[must_use]
+    pub fn precedes(&self, version: &ParsedVersion) -> bool {
+        let major = version.release.first().copied().unwrap_or_default();
+        let minor = version.release.get(1).copied().unwrap_or_default();
+        major < self.major || (major == self.major && self.minor.is_some_and(|m| minor < m))
+    }
+
+    /// Returns whether the tag with the given name belongs to the release
+    /// line.
+    #[must_use]
+    pub fn contains_tag(&self, name: &str, scheme: &dyn VersionScheme) -> bool {
+        scheme
+            .parse(name)
+            .is_some_and(|version| self.contains(&version))
+    }
+
+    /// Keeps only the tags that belong to the release line.
+    pub fn retain_tags(&self, tags: &mut IndexMap<String, Tag>, scheme: &dyn VersionScheme) {
+        tags.retain(|_, tag| self.contains_tag(&tag.name, scheme));
+    }
+
+    /// Keeps the tags that belong to the release line or precede it.
+    ///
+    /// The preceding tags are kept as the boundaries of the earlier history,
+    /// which is skipped since their versions are not in the line.
+    pub fn retain_boundary_tags(
+        &self,
+        tags: &mut IndexMap<String, Tag>,
+        scheme: &dyn VersionScheme,
+    ) {
+        tags.retain(|_, tag| {
+            scheme
+                .parse(&tag.name)
+                .is_some_and(|version| self.contains(&version) || self.precedes(&version))
+        });
+    }
+}
+
+impl fmt::Display for ReleaseLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.minor {
+            Some(minor) => write!(f, "{}.{minor}.x", self.major),
+            None => write!(f, "{}.x", self.major),
+        }
+    }
+}
+
+/// Parses the given version with the given parser, skipping the non-numeric
+/// prefix of the version (e.g. `v`, `app-v` or `my-app2-v`).
+///
+/// The parser is tried at the start of each numeric part of the version until
+/// it succeeds.
+fn parse_with_prefix(
+    version: &str,
+    parse: impl Fn(&str) -> Option<ParsedVersion>,
+) -> Option<ParsedVersion> {
+    version
+        .char_indices()
+        .filter(|(i, c)| {
+            c.is_ascii_digit() && !version[..*i].ends_with(|c: char| c.is_ascii_digit())
+        })
+        .find_map(|(i, _)| {
+            parse(&version[i..]).map(|parsed| ParsedVersion {
+                prefix: version[..i].to_string(),
+                ..parsed
+            })
+        })
+}
+
+/// Returns the first three components of the version, padded with zeros.
+fn padded_release(version: &ParsedVersion) -> [u64; 3] {
+    let component = |i: usize| version.release.get(i).copied().unwrap_or_default();
+    [component(0), component(1), component(2)]
+}
+
+/// Returns the components of the given semantic version.
+///
+/// Trailing zero components are omitted unless they are needed for having at
+/// least `min_length` components.
+fn release_from_semver(version: &Version, min_length: usize) -> Vec<u64> {
+    let length = if version.patch != 0 {
+        3
+    } else if version.minor != 0 {
+        2
+    } else {
+        1
+    };
+    let mut release = vec![version.major, version.minor, version.patch];
+    release.resize(length.max(min_length), 0);
+    release
+}
+
+/// Joins the components of the version with dots.
+fn join_release(release: &[u64]) -> String {
+    release
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Compares the dot separated pre-release identifiers of semantic versions.
+///
+/// <https://semver.org/#spec-item-11>
+fn compare_identifiers(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => {
+                let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => a.cmp(b),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Normalizes the given PEP 440 pre-release label to `a`, `b` or `rc`.
+fn normalize_pep440_label(label: &str) -> &'static str {
+    match label.to_lowercase().as_str() {
+        "a" | "alpha" => "a",
+        "b" | "beta" => "b",
+        _ => "rc",
+    }
+}
+
+/// Splits the normalized PEP 440 pre-release into its label and number.
+fn split_pep440_pre(pre: &str) -> (&str, u64) {
+    let index = pre.find(|c: char| c.is_ascii_digit()).unwrap_or(pre.len());
+    (&pre[..index], pre[index..].parse().unwrap_or_default())
+}
+
+/// Returns the version of the previous release.
+///
+/// Falls back to the manifest version if there is no previous release.
+fn previous_version(release: &Release, config: &Bump) -> Option<String> {
+    release
+        .previous
+        .as_ref()
+        .and_then(|release| release.version.clone())
+        .or_else(|| config.manifest_version.clone())
+}
+
+/// Returns the error for the given invalid version.
+fn invalid_version(version: &str) -> Error {
+    Error::ChangelogError(format!("Cannot parse the version: {version}"))
+}
+
+/// Increments the version based on the commits of the release.
+///
+/// If the pull requests of the commits have labels that are configured in the
+/// label rules, the highest level of the labels is applied.
+///
+/// Otherwise, the increment of each commit is calculated via the
+/// [`VersionUpdater`] and limited by the bump rules that match the commit, and
+/// the highest increment is applied.
+///
+/// The version is incremented at least by a patch if the release has
+/// dependency updates.
+///
+/// Returns the next version along with the reasons of the increment.
+fn increment(
+    version: &Version,
+    release: &Release,
+    config: &Bump,
+) -> Result<(Version, Vec<BumpReason>)> {
+    let reason = |commit: &Commit, rule: String| BumpReason {
+        id: commit.id.clone(),
+        message: commit
+            .message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        rule,
+    };
+    let (level, reasons) = if let Some(level) = label_level(release, config) {
+        tracing::debug!("Bumping the version based on the pull request labels: {level:?}");
+        let reasons = release
+            .commits
+            .iter()
+            .filter_map(|commit| {
+                let label = commit
+                    .remote
+                    .as_ref()?
+                    .pr_labels
+                    .iter()
+                    .find(|label| config.label_rules.get(*label) == Some(&level))?;
+                Some(reason(commit, format!("label: {label}")))
+            })
+            .collect();
+        (level, reasons)
+    } else {
+        let levels = commit_levels(version, release, config)?;
+        let level = levels
+            .iter()
+            .map(|(level, _)| *level)
+            .max()
+            .unwrap_or(BumpLevel::None);
+        let reasons = release
+            .commits
+            .iter()
+            .zip(levels)
+            .filter(|(_, (commit_level, _))| level != BumpLevel::None && *commit_level == level)
+            .map(|(commit, (_, rule))| reason(commit, rule))
+            .collect();
+        (level, reasons)
+    };
+    if level == BumpLevel::None && !release.dependency_updates.is_empty() {
+        tracing::debug!("Bumping the version for the updated dependencies");
+        let reasons = release
+            .dependency_updates
+            .iter()
+            .map(|dependency| BumpReason {
+                id: String::new(),
+                message: format!("update {} to {}", dependency.name, dependency.version),
+                rule: String::from("dependency"),
+            })
+            .collect();
+        return Ok((increment_level(version, DEPENDENCY_BUMP_LEVEL), reasons));
+    }
+    Ok((increment_level(version, level), reasons))
+}
+
+/// Calculates the increment of each commit of the release.
+///
+/// The increments are calculated via the [`VersionUpdater`], limited by the
+/// bump rules that match the commits and returned along with the name of the
+/// rule that decided them.
+fn commit_levels(
+    version: &Version,
+    release: &Release,
+    config: &Bump,
+) -> Result<Vec<(BumpLevel, String)>> {
+    let rule_names = RuleNames::new(config)?;
+    let mut levels = Vec::with_capacity(release.commits.len());
+    for commit in &release.commits {
+        let message = commit.raw_message().trim_end();
+        let next = version_updater(config)?.increment(version, [message]);
+        let mut level = if next == *version {
+            BumpLevel::None
+        } else {
+            match determine_bump_type(version, &next) {
+                Some(BumpType::Major) => BumpLevel::Major,
+                Some(BumpType::Minor) => BumpLevel::Minor,
+                // The pre-release number is incremented.
+                _ => BumpLevel::Patch,
+            }
+        };
+        let mut rule = rule_names.rule(message).to_string();
+        for (index, bump_rule) in config.rules.iter().enumerate() {
+            if bump_rule.matches(commit) && bump_rule.max < level {
+                tracing::trace!("Limiting the bump of {} to {:?}", commit.id, bump_rule.max);
+                level = bump_rule.max;
+                rule = format!("bump.rules[{index}]");
+            }
+        }
+        levels.push((level, rule));
+    }
+    Ok(levels)
+}
+
+/// Names of the rules that decide the increments of the commits.
+#[derive(Debug)]
+struct RuleNames {
+    custom_major_increment_regex: Option<Regex>,
+    custom_minor_increment_regex: Option<Regex>,
+}
+
+impl RuleNames {
+    /// Compiles the custom increment regexes of the given bump configuration.
+    fn new(config: &Bump) -> Result<Self> {
+        let regex = |value: Option<&str>| value.map(Regex::new).transpose();
+        Ok(Self {
+            custom_major_increment_regex: regex(config.custom_major_increment_regex.as_deref())?,
+            custom_minor_increment_regex: regex(config.custom_minor_increment_regex.as_deref())?,
+        })
+    }
+
+    /// Returns the name of the rule that decides the increment of the given
+    /// commit message.
+    fn rule(&self, message: &str) -> &'static str {
+        let conventional = git_conventional::Commit::parse(message).ok();
+        // The custom regexes are matched against the type of the conventional
+        // commits and the whole message of the others.
+        let part = conventional
+            .as_ref()
+            .map_or(message, |commit| commit.type_().as_str());
+        let matches = |regex: Option<&Regex>| regex.is_some_and(|regex| regex.is_match(part));
+        if conventional
+            .as_ref()
+            .is_some_and(|commit| commit.breaking())
+        {
+            "breaking change"
+        } else if matches(self.custom_major_increment_regex.as_ref()) {
+            "custom_major_increment_regex"
+        } else if conventional
+            .as_ref()
+            .is_some_and(|commit| commit.type_() == git_conventional::Type::FEAT)
+        {
+            "feature"
+        } else if matches(self.custom_minor_increment_regex.as_ref()) {
+            "custom_minor_increment_regex"
+        } else {
+            "other change"
+        }
+    }
+}
+
+/// Returns the highest level of the pull request labels in the release.
+///
+/// Returns `None` if none of the labels are configured in the label rules.
+fn label_level(release: &Release, config: &Bump) -> Option<BumpLevel> {
+    release
+        .commits
+        .iter()
+        .filter_map(|commit| commit.remote.as_ref())
+        .flat_map(|remote| &remote.pr_labels)
+        .filter_map(|label| config.label_rules.get(label).copied())
+        .max()
+}
+
+/// Increments the given version by the given level.
+///
+/// The pre-release versions only get their pre-release number incremented,
+/// the same way as the [`VersionUpdater`] does.
+fn increment_level(version: &Version, level: BumpLevel) -> Version {
+    match level {
+        BumpLevel::None => version.clone(),
+        _ if !version.pre.is_empty() => version.increment_prerelease(),
+        BumpLevel::Major => version.increment_major(),
+        BumpLevel::Minor => version.increment_minor(),
+        BumpLevel::Patch => version.increment_patch(),
+    }
+}
+
+/// Returns the version updater with the given bump configuration.
+fn version_updater(config: &Bump) -> Result<VersionUpdater> {
+    let mut updater = VersionUpdater::new()
+        .with_features_always_increment_minor(config.features_always_bump_minor.unwrap_or(true))
+        .with_breaking_always_increment_major(config.breaking_always_bump_major.unwrap_or(true));
+    if let Some(custom_major_increment_regex) = &config.custom_major_increment_regex {
+        updater = updater.with_custom_major_increment_regex(custom_major_increment_regex)?;
+    }
+    if let Some(custom_minor_increment_regex) = &config.custom_minor_increment_regex {
+        updater = updater.with_custom_minor_increment_regex(custom_minor_increment_regex)?;
+    }
+    if let Some(no_increment_regex) = &config.no_increment_regex {
+        updater = updater.with_no_increment_regex(no_increment_regex)?;
+    }
+    Ok(updater)
+}
+
+/// Calculates the next pre-release version.
+///
+/// If the current version is a pre-release with the same identifier, the
+/// pre-release number is incremented (e.g. `1.4.0-rc.1` -> `1.4.0-rc.2`).
+/// Otherwise, a new pre-release is started for the next normal version, which
+/// is calculated via `next_normal` (e.g. `1.3.2` -> `1.4.0-rc.1`).
+fn next_prerelease(
+    current: &Version,
+    identifier: Option<&str>,
+    next_normal: impl FnOnce() -> Result<Version>,
+) -> Result<Version> {
+    let current_identifier = current
+        .pre
+        .as_str()
+        .split('.')
+        .next()
+        .filter(|v| !v.is_empty());
+    let identifier = identifier
+        .or(current_identifier)
+        .unwrap_or(DEFAULT_PRERELEASE_IDENTIFIER);
+    if current_identifier == Some(identifier) {
+        return Ok(current.increment_prerelease());
+    }
+    let mut version = if current.pre.is_empty() {
+        let version = next_normal()?;
+        if &version == current {
+            current.increment_patch()
+        } else {
+            version
+        }
+    } else {
+        current.clone()
+    };
+    version.pre = Prerelease::new(&format!("{identifier}.1"))?;
+    Ok(version)
+}
+
+/// Determines the bump type by comparing two semver versions.
+fn determine_bump_type(old: &Version, new: &Version) -> Option<BumpType> {
+    if new.major != old.major {
+        Some(BumpType::Major)
+    } else if new.minor != old.minor {
+        Some(BumpType::Minor)
+    } else if new.patch != old.patch {
+        Some(BumpType::Patch)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use glob::Pattern;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::config::BumpRule;
+    use crate::contributor::RemoteContributor;
+    use crate::release::DependencyUpdate;
+
+    fn build_release<'a>(version: &str, commits: &[&str]) -> Release<'a> {
+        Release {
+            commits: commits
+                .iter()
+                .map(|v| Commit::from((*v).to_string()))
+                .collect(),
+            previous: Some(Box::new(Release {
+                version: Some(String::from(version)),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_semver() {
+        assert_eq!(
+            Some(ParsedVersion {
+                prefix: String::from("app-v"),
+                release: vec![1, 2, 3],
+                pre: Some(String::from("rc.1")),
+                build: Some(String::from("build.5")),
+                ..Default::default()
+            }),
+            Semver.parse("app-v1.2.3-rc.1+build.5")
+        );
+        assert_eq!(
+            Some(ParsedVersion {
+                release: vec![1, 2],
+                pre: Some(String::from("SNAPSHOT")),
+                ..Default::default()
+            }),
+            Semver.parse("1.2-SNAPSHOT")
+        );
+        assert_eq!(None, Semver.parse("2.0.0a3"));
+        assert_eq!(
+            "v1.2-SNAPSHOT",
+            Semver.format(&Semver.parse("v1.2-SNAPSHOT").expect("valid version"))
+        );
+    }
+
+    #[test]
+    fn compare_semver() {
+        for (a, b) in [
+            ("1.0.0-alpha", "1.0.0-alpha.1"),
+            ("1.0.0-alpha.1", "1.0.0-alpha.beta"),
+            ("1.0.0-beta.2", "1.0.0-beta.11"),
+            ("1.0.0-rc.1", "1.0.0"),
+            ("v1.2-SNAPSHOT", "v1.2"),
+            ("1.2", "1.2.1"),
+            ("invalid", "0.1.0"),
+        ] {
+            assert_eq!(Ordering::Less, Semver.compare_str(a, b), "{a} < {b}");
+        }
+        assert_eq!(Ordering::Equal, Semver.compare_str("v1.2", "1.2.0"));
+    }
+
+    #[test]
+    fn bump_semver() -> Result<()> {
+        for (version, commits, expected) in [
+            ("1.2-SNAPSHOT", vec!["feat: add xyz"], "1.2"),
+            ("v1.2", vec!["feat: add xyz"], "v1.3"),
+            ("v1.2", vec!["fix: fix xyz"], "v1.2.1"),
+            ("release-1.2.3", vec!["feat!: add xyz"], "release-2.0.0"),
+        ] {
+            let release = build_release(version, &commits);
+            assert_eq!(expected, Semver.bump(&release, &Bump::default())?.version);
+        }
+        assert!(
+            Semver
+                .bump(&build_release("2.0.0a3", &[]), &Bump::default())
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bump_with_rules() -> Result<()> {
+        let config = Bump {
+            rules: vec![
+                BumpRule {
+                    scope: Some(Regex::new("^(internal|test)$")?),
+                    paths: vec![],
+                    max: BumpLevel::Patch,
+                },
+                BumpRule {
+                    scope: None,
+                    paths: vec![Pattern::new("tools/**")?],
+                    max: BumpLevel::None,
+                },
+            ],
+            ..Default::default()
+        };
+        let bump = |commits: &[(&str, &[&str])]| -> Result<String> {
+            let mut release = build_release("v1.2.3", &[]);
+            for (message, files) in commits {
+                let mut commit = Commit::from((*message).to_string()).into_conventional()?;
+                commit.changed_files = files.iter().map(|v| (*v).to_string()).collect();
+                release.commits.push(commit);
+            }
+            Ok(Semver.bump(&release, &config)?.version)
+        };
+
+        assert_eq!("v2.0.0", bump(&[("feat(api)!: add xyz", &["src/lib.rs"])])?);
+        assert_eq!(
+            "v1.2.4",
+            bump(&[("feat(internal)!: add xyz", &["src/lib.rs"])])?
+        );
+        assert_eq!(
+            "v1.2.3",
+            bump(&[("feat!: add xyz", &["tools/a.rs", "tools/b.rs"])])?
+        );
+        assert_eq!(
+            "v2.0.0",
+            bump(&[("feat!: add xyz", &["tools/a.rs", "src/lib.rs"])])?
+        );
+        assert_eq!(
+            "v1.3.0",
+            bump(&[
+                ("feat(test)!: add xyz", &["tests/a.rs"]),
+                ("feat: add abc", &["src/lib.rs"]),
+                ("feat(internal)!: add abc", &["tools/a.rs"]),
+            ])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bump_with_dependency_updates() -> Result<()> {
+        let config = Bump::default();
+        let mut release = build_release("v1.2.3", &[]);
+        release.dependency_updates = vec![DependencyUpdate {
+            name: String::from("core"),
+            version: String::from("core-v0.2.0"),
+        }];
+        let next = Semver.bump(&release, &config)?;
+        assert_eq!("v1.2.4", next.version);
+        assert_eq!(Some(BumpType::Patch), next.bump_type);
+        assert_eq!(
+            vec![BumpReason {
+                id: String::new(),
+                message: String::from("update core to core-v0.2.0"),
+                rule: String::from("dependency"),
+            }],
+            next.commits
+        );
+
+        let mut release = build_release("v1.2.3", &["feat: add xyz"]);
+        release.dependency_updates = vec![DependencyUpdate::default()];
+        let next = Semver.bump(&release, &config)?;
+        assert_eq!("v1.3.0", next.version);
+        assert_eq!("feature", next.commits[0].rule);
+
+        let release = build_release("1.2.3", &[]);
+        assert_eq!("1.2.3", Pep440.bump(&release, &config)?.version);
+        let mut release = build_release("1.2.3", &[]);
+        release.dependency_updates = vec![DependencyUpdate::default()];
+        assert_eq!("1.2.4", Pep440.bump(&release, &config)?.version);
+        Ok(())
+    }
+
+    #[test]
+    fn bump_with_reasons() -> Result<()> {
+        let config = Bump {
+            rules: vec![BumpRule {
+                scope: Some(Regex::new("^internal$")?),
+                paths: vec![],
+                max: BumpLevel::Patch,
+            }],
+            ..Default::default()
+        };
+        let bump = |commits: &[(&str, &str)]| -> Result<NextVersion> {
+            let mut release = build_release("v1.2.3", &[]);
+            for (id, message) in commits {
+                let commit = Commit::new((*id).to_string(), (*message).to_string());
+                release.commits.push(commit.into_conventional()?);
+            }
+            Semver.bump(&release, &config)
+        };
+        let reason = |id: &str, message: &str, rule: &str| BumpReason {
+            id: id.to_string(),
+            message: message.to_string(),
+            rule: rule.to_string(),
+        };
+
+        let next = bump(&[
+            ("a1", "feat: add xyz"),
+            ("b2", "fix: fix abc"),
+            ("c3", "feat(internal)!: change api\n\nbody"),
+            ("d4", "feat: add abc"),
+        ])?;
+        assert_eq!(Some(String::from("v1.2.3")), next.previous_version);
+        assert_eq!("v1.3.0", next.version);
+        assert_eq!(Some(BumpType::Minor), next.bump_type);
+        assert_eq!(
+            vec![
+                reason("a1", "feat: add xyz", "feature"),
+                reason("d4", "feat: add abc", "feature"),
+            ],
+            next.commits
+        );
+
+        let next = bump(&[("a1", "feat: add xyz"), ("e5", "refactor!: drop xyz")])?;
+        assert_eq!("v2.0.0", next.version);
+        assert_eq!(
+            vec![reason("e5", "refactor!: drop xyz", "breaking change")],
+            next.commits
+        );
+
+        let next = bump(&[
+            ("b2", "fix: fix abc"),
+            ("c3", "feat(internal)!: change api"),
+        ])?;
+        assert_eq!("v1.2.4", next.version);
+        assert_eq!(
+            vec![
+                reason("b2", "fix: fix abc", "other change"),
+                reason("c3", "feat(internal)!: change api", "bump.rules[0]"),
+            ],
+            next.commits
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bump_with_label_rules() -> Result<()> {
+        let config = Bump {
+            label_rules: HashMap::from([
+                (String::from("semver:major"), BumpLevel::Major),
+                (String::from("semver:minor"), BumpLevel::Minor),
+                (String::from("semver:none"), BumpLevel::None),
+            ]),
+            ..Default::default()
+        };
+        let bump = |commits: &[(&str, &[&str])]| -> Result<String> {
+            let mut release = build_release("v1.2.3", &[]);
+            for (message, labels) in commits {
+                let mut commit = Commit::from((*message).to_string());
+                commit.remote = Some(RemoteContributor {
+                    pr_labels: labels.iter().map(|v| (*v).to_string()).collect(),
+                    ..Default::default()
+                });
+                release.commits.push(commit);
+            }
+            Ok(Semver.bump(&release, &config)?.version)
+        };
+
+        assert_eq!("v1.2.3", bump(&[("feat!: add xyz", &["semver:none"])])?);
+        assert_eq!(
+            "v2.0.0",
+            bump(&[
+                ("fix xyz", &["semver:minor", "bug"]),
+                ("add xyz", &["semver:major"]),
+            ])?
+        );
+        assert_eq!("v1.3.0", bump(&[("update xyz", &["semver:minor"])])?);
+        assert_eq!("v1.3.0", bump(&[("feat: add xyz", &["enhancement"])])?);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_pep440() {
+        assert_eq!(
+            Some(ParsedVersion {
+                prefix: String::from("v"),
+                epoch: Some(1),
+                release: vec![2, 0, 0],
+                pre: Some(String::from("a3")),
+                post: Some(2),
+                dev: Some(1),
+                build: Some(String::from("ubuntu.1")),
+            }),
+            Pep440.parse("v1!2.0.0-alpha.3.post2.dev1+ubuntu-1")
+        );
+        for (version, expected) in [
+            ("1.2.0.post1", "1.2.0.post1"),
+            ("1.2.0-1", "1.2.0.post1"),
+            ("2.0.0a3", "2.0.0a3"),
+            ("2.0.0.RC1", "2.0.0rc1"),
+            ("2.0.0beta", "2.0.0b0"),
+            ("1.0.dev", "1.0.dev0"),
+        ] {
+            assert_eq!(
+                Some(expected.to_string()),
+                Pep440.parse(version).map(|v| Pep440.format(&v))
+            );
+        }
+        assert_eq!(None, Pep440.parse("1.2-SNAPSHOT"));
+    }
+
+    #[test]
+    fn compare_pep440() {
+        let versions = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0rc1.dev456",
+            "1.0rc1",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.0.15",
+            "1.1.dev1",
+            "1!0.1",
+        ];
+        for window in versions.windows(2) {
+            assert_eq!(
+                Ordering::Less,
+                Pep440.compare_str(window[0], window[1]),
+                "{} < {}",
+                window[0],
+                window[1]
+            );
+        }
+        assert_eq!(Ordering::Equal, Pep440.compare_str("1.0", "1.0.0"));
+    }
+
+    #[test]
+    fn bump_pep440() -> Result<()> {
+        let prerelease = Bump {
+            bump_type: Some(BumpType::Prerelease),
+            ..Default::default()
+        };
+        let promote = Bump {
+            bump_type: Some(BumpType::Release),
+            ..Default::default()
+        };
+        let with_identifier = Bump {
+            prerelease_identifier: Some(String::from("rc")),
+            ..Default::default()
+        };
+        for (version, commits, config, expected) in [
+            (
+                "1.2.0.post1",
+                vec!["fix: fix xyz"],
+                &Bump::default(),
+                "1.2.1",
+            ),
+            ("v1.2.0", vec!["feat: add xyz"], &Bump::default(), "v1.3.0"),
+            ("1.2", vec!["feat!: add xyz"], &Bump::default(), "2.0"),
+            ("2.0.0a3", vec!["fix: fix xyz"], &Bump::default(), "2.0.0a4"),
+            ("2.0.0a3", vec!["fix: fix xyz"], &prerelease, "2.0.0a4"),
+            ("2.0.0a3", vec!["fix: fix xyz"], &promote, "2.0.0"),
+            (
+                "2.0.0rc2",
+                vec!["fix: fix xyz"],
+                &with_identifier,
+                "2.0.0rc3",
+            ),
+            ("1.0.0.dev3", vec!["fix: fix xyz"], &promote, "1.0.0"),
+            ("1.2.0", vec!["feat: add xyz"], &promote, "1.3.0"),
+            ("1.3.2", vec!["feat: add xyz"], &prerelease, "1.4.0rc1"),
+            (
+                "1.4.0rc1",
+                vec!["fix: fix xyz"],
+                &Bump {
+                    prerelease_identifier: Some(String::from("beta")),
+                    ..prerelease.clone()
+                },
+                "1.4.0b1",
+            ),
+            (
+                "1.0.0.dev3",
+                vec!["fix: fix xyz"],
+                &Bump::default(),
+                "1.0.0",
+            ),
+        ] {
+            let release = build_release(version, &commits);
+            assert_eq!(expected, Pep440.bump(&release, config)?.version);
+        }
+
+        let release = build_release("1.2.0", &["feat: add xyz"]);
+        let next = Pep440.bump(&release, &Bump {
+            bump_type: Some(BumpType::Major),
+            ..Default::default()
+        })?;
+        assert_eq!("2.0.0", next.version);
+        assert_eq!(Some(BumpType::Major), next.bump_type);
+        Ok(())
+    }
+
+    #[test]
+    fn bump_prerelease_with_rules() -> Result<()> {
+        let with_rules = Bump {
+            rules: vec![BumpRule {
+                scope: Some(Regex::new("^internal$")?),
+                paths: vec![],
+                max: BumpLevel::None,
+            }],
+            ..Default::default()
+        };
+        let bump = |messages: &[&str], config: &Bump| -> Result<NextVersion> {
+            let mut release = build_release("v1.4.0-rc.2", &[]);
+            for message in messages {
+                let commit = Commit::new(String::from("a1"), (*message).to_string());
+                release.commits.push(commit.into_conventional()?);
+            }
+            Semver.bump(&release, config)
+        };
+
+        for config in [&Bump::default(), &with_rules] {
+            let next = bump(&["feat: add xyz"], config)?;
+            assert_eq!("v1.4.0-rc.3", next.version);
+            assert_eq!(
+                vec![BumpReason {
+                    id: String::from("a1"),
+                    message: String::from("feat: add xyz"),
+                    rule: String::from("feature"),
+                }],
+                next.commits
+            );
+        }
+        let next = bump(&["feat(internal): add xyz"], &with_rules)?;
+        assert_eq!("v1.4.0-rc.2", next.version);
+        assert!(next.commits.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn bump_from_manifest_version() -> Result<()> {
+        let release = Release {
+            commits: vec![
+                Commit::new(String::from("a1"), String::from("feat: add xyz"))
+                    .into_conventional()?,
+            ],
+            ..Default::default()
+        };
+        let mut config = Bump {
+            initial_tag: Some(String::from("v0.1.0")),
+            ..Default::default()
+        };
+        assert_eq!("v0.1.0", Semver.bump(&release, &config)?.version);
+
+        config.manifest_version = Some(String::from("v1.2.3"));
+        let next = Semver.bump(&release, &config)?;
+        assert_eq!(Some(String::from("v1.2.3")), next.previous_version);
+        assert_eq!("v1.3.0", next.version);
+        Ok(())
+    }
+
+    #[test]
+    fn release_line() {
+        for (value, expected) in [
+            ("1.x", Some((1, None))),
+            ("release/1.2.x", Some((1, Some(2)))),
+            ("v10.X", Some((10, None))),
+            ("main", None),
+            ("1.2.3", None),
+        ] {
+            assert_eq!(
+                expected.map(|(major, minor)| ReleaseLine { major, minor }),
+                ReleaseLine::parse(value),
+                "{value}"
+            );
+        }
+
+        let line = ReleaseLine::parse("release/1.x").expect("valid release line");
+        assert_eq!("1.x", line.to_string());
+        let build_tags = || {
+            ["v1.0.0", "v1.2.1", "v2.0.0", "invalid"]
+                .into_iter()
+                .map(|name| {
+                    (name.to_string(), Tag {
+                        name: name.to_string(),
+                        ..Default::default()
+                    })
+                })
+                .collect::<IndexMap<String, Tag>>()
+        };
+        let mut tags = build_tags();
+        let mut boundary_tags = build_tags();
+        line.retain_tags(&mut tags, &Semver);
+        assert_eq!(
+            vec!["v1.0.0", "v1.2.1"],
+            tags.keys().collect::<Vec<&String>>()
+        );
+
+        let line = ReleaseLine::parse("1.2.x").expect("valid release line");
+        line.retain_tags(&mut tags, &Semver);
+        assert_eq!(vec!["v1.2.1"], tags.keys().collect::<Vec<&String>>());
+        line.retain_boundary_tags(&mut boundary_tags, &Semver);
+        assert_eq!(
+            vec!["v1.0.0", "v1.2.1"],
+            boundary_tags.keys().collect::<Vec<&String>>()
+        );
+        assert!(line.contains_tag("v1.2.1", &Semver));
+        assert!(!line.contains_tag("v1.0.0", &Semver));
+        assert!(!line.contains_tag("invalid", &Semver));
+    }
+
+    #[test]
+    fn compare_calver() -> Result<()> {
+        let scheme = Calver::new(CalverFormat::parse("YYYY.0M.MICRO")?);
+        assert_eq!(
+            Ordering::Less,
+            scheme.compare_str("v2025.12.4", "v2026.01.0")
+        );
+        assert_eq!(
+            Ordering::Less,
+            scheme.compare_str("2026.01.2", "2026.01.10")
+        );
+        assert_eq!(Ordering::Less, scheme.compare_str("1.2.3", "2026.01.0"));
+        assert_eq!(
+            "v2026.01.10",
+            scheme.format(&scheme.parse("v2026.01.10").expect("valid version"))
+        );
+        Ok(())
+    }
+}
@@ -2,7 +2,10 @@ use std::collections::HashMap;
 use std::fmt::Write;
 
 use git_cliff_core::commit::{Commit, Range, Signature};
-use git_cliff_core::config::{ChangelogConfig, CommitParser, GitConfig, LinkParser, TextProcessor};
+use git_cliff_core::config::{
+    ChangelogConfig, CommitDate, CommitParser, GitConfig, LinkParser, ReleaseDate, TagSort,
+    TextProcessor,
+};
 use git_cliff_core::error::Result;
 use git_cliff_core::release::*;
 use git_cliff_core::template::Template;
@@ -37,6 +40,10 @@ fn generate_changelog() -> Result<()> {
         render_always: false,
         postprocessors: [].to_vec(),
         output: None,
+        yanked: vec![],
+        limit_releases: None,
+        archive_output: None,
+        archive_link: None,
     };
     let git_config = GitConfig {
         processing_order: None,
@@ -114,13 +121,20 @@ fn generate_changelog() -> Result<()> {
         protect_breaking_commits: false,
         filter_commits: true,
         fail_on_unmatched_commit: false,
+        require_signed_commits: false,
         tag_pattern: None,
         skip_tags: None,
         ignore_tags: None,
         count_tags: None,
+        collapse_prereleases: false,
         use_branch_tags: false,
+        release_line: None,
         topo_order: false,
+        tag_sort: TagSort::Time,
+        release_date: ReleaseDate::Commit,
+        commit_date: CommitDate::Committer,
         topo_order_commits: true,
+        first_parent: false,
         sort_commits: String::from("oldest"),
         link_parsers: vec![
             LinkParser {
@@ -138,6 +152,9 @@ fn generate_changelog() -> Result<()> {
         recurse_submodules: None,
         include_paths: Vec::new(),
         exclude_paths: Vec::new(),
+        include_authors: Vec::new(),
+        exclude_authors: Vec::new(),
+        mailmap_file: None,
     };
 
     let mut commit_with_author = Commit::new(
@@ -229,6 +246,14 @@ fn generate_changelog() -> Result<()> {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: git_cliff_core::remote::RemoteReleaseMetadata {
                 contributors: vec![],
@@ -263,6 +288,14 @@ fn generate_changelog() -> Result<()> {
             submodule_commits: HashMap::new(),
             statistics: None,
             bump_type: None,
+            signed: false,
+            signature_type: None,
+            signer_key: None,
+            yanked: false,
+            tagger: None,
+            tag_timestamp: None,
+            dependency_updates: Vec::new(),
+            prereleases: Vec::new(),
             #[cfg(feature = "github")]
             github: git_cliff_core::remote::RemoteReleaseMetadata {
                 contributors: vec![],
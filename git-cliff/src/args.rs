@@ -1,9 +1,10 @@
 use std::path::PathBuf;
 
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use clap::builder::styling::{Ansi256Color, AnsiColor};
 use clap::builder::{Styles, TypedValueParser, ValueParserFactory};
 use clap::error::{ContextKind, ContextValue, ErrorKind};
-use clap::{ArgAction, Parser, ValueEnum};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 use git_cliff_core::config::{BumpType, Remote};
 use git_cliff_core::{DEFAULT_CONFIG, DEFAULT_OUTPUT};
 use glob::Pattern;
@@ -24,6 +25,12 @@ pub enum Sort {
     Newest,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
 const STYLES: Styles = Styles::styled()
     .header(Ansi256Color(208).on_default().bold())
     .usage(Ansi256Color(208).on_default().bold())
@@ -33,6 +40,7 @@ const STYLES: Styles = Styles::styled()
 #[derive(Debug, Parser, Clone)]
 #[command(
     version,
+    propagate_version = true,
     author = clap::crate_authors!("\n"),
     about,
     rename_all_env = "screaming-snake",
@@ -132,6 +140,22 @@ pub struct Opt {
 		num_args(1..)
 	)]
     pub exclude_path: Option<Vec<Pattern>>,
+    /// Sets the name or email of the authors to include the commits of.
+    #[arg(
+		long,
+		env = "GIT_CLIFF_AUTHOR",
+		value_name = "PATTERN",
+		num_args(1..)
+	)]
+    pub author: Option<Vec<Pattern>>,
+    /// Sets the name or email of the authors to exclude the commits of.
+    #[arg(
+		long,
+		env = "GIT_CLIFF_EXCLUDE_AUTHOR",
+		value_name = "PATTERN",
+		num_args(1..)
+	)]
+    pub exclude_author: Option<Vec<Pattern>>,
     /// Sets the regex for matching git tags.
     #[arg(long, env = "GIT_CLIFF_TAG_PATTERN", value_name = "PATTERN")]
     pub tag_pattern: Option<Regex>,
@@ -160,6 +184,9 @@ pub struct Opt {
     /// Sets the tags to count in the changelog.
     #[arg(long, env = "GIT_CLIFF_COUNT_TAGS", value_name = "PATTERN")]
     pub count_tags: Option<Regex>,
+    /// Sets the release line to include the tags of (e.g. 1.x or branch).
+    #[arg(long, env = "GIT_CLIFF_RELEASE_LINE", value_name = "LINE")]
+    pub release_line: Option<String>,
     /// Sets commits that will be skipped in the changelog.
     #[arg(
 		long,
@@ -190,6 +217,15 @@ pub struct Opt {
 	    default_missing_value = DEFAULT_OUTPUT
 	)]
     pub output: Option<PathBuf>,
+    /// Sets the number of releases to keep in the changelog.
+    #[arg(long, env = "GIT_CLIFF_LIMIT_RELEASES", value_name = "N")]
+    pub limit_releases: Option<usize>,
+    /// Processes the commits more recent than a date.
+    #[arg(long, env = "GIT_CLIFF_SINCE", value_name = "DATE", value_parser = Opt::parse_since)]
+    pub since: Option<i64>,
+    /// Processes the commits older than a date.
+    #[arg(long, env = "GIT_CLIFF_UNTIL", value_name = "DATE", value_parser = Opt::parse_until)]
+    pub until: Option<i64>,
     /// Sets the tag for the latest version.
     #[arg(
         short,
@@ -212,6 +248,30 @@ pub struct Opt {
     /// Prints bumped version for unreleased changes.
     #[arg(long, help_heading = Some("FLAGS"))]
     pub bumped_version: bool,
+    /// Sets the output format of the bumped version.
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        requires = "bumped_version"
+    )]
+    pub output_format: OutputFormat,
+    /// Writes bumped version into the configured version files.
+    #[arg(long, help_heading = Some("FLAGS"))]
+    pub write_version: bool,
+    /// Creates an annotated tag for the bumped version.
+    #[arg(long, help_heading = Some("FLAGS"))]
+    pub create_tag: bool,
+    /// Commits the changelog and the version files.
+    #[arg(long, help_heading = Some("FLAGS"))]
+    pub commit: bool,
+    /// Allows committing with a dirty index.
+    #[arg(long, requires = "commit", help_heading = Some("FLAGS"))]
+    pub allow_dirty: bool,
+    /// Sets the identifier of the pre-release versions (e.g. rc).
+    #[arg(long, value_name = "IDENTIFIER")]
+    pub preid: Option<String>,
     /// Sets the template for the changelog body.
     #[arg(
         short,
@@ -382,6 +442,52 @@ pub struct Opt {
     /// Disable network access for remote repositories.
     #[arg(long, env = "GIT_CLIFF_OFFLINE", help_heading = Some("REMOTE OPTIONS"), hide = !cfg!(feature = "remote"))]
     pub offline: bool,
+    /// Subcommand to run.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Lints the commit messages using the configuration.
+    Lint(LintOpt),
+    /// Installs a commit-msg hook for linting the commit messages.
+    InstallHook {
+        /// Overwrites the existing hook.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Records the given version as yanked in the configuration file.
+    Yank(YankOpt),
+}
+
+/// Command-line arguments of the `lint` subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct LintOpt {
+    /// Reads the commit message from the given file.
+    #[arg(
+	    long,
+	    value_name = "PATH",
+	    value_parser = Opt::parse_dir,
+	    conflicts_with = "range"
+	)]
+    pub message_file: Option<PathBuf>,
+    /// Sets the commit range to lint.
+    #[arg(value_name = "RANGE")]
+    pub range: Option<String>,
+}
+
+/// Command-line arguments of the `yank` subcommand.
+#[derive(Debug, Clone, Args)]
+pub struct YankOpt {
+    /// Sets the version to yank.
+    #[arg(
+        id = "yank_version",
+        value_name = "VERSION",
+        allow_hyphen_values = true
+    )]
+    pub version: String,
 }
 
 /// Custom type for the remote value.
@@ -464,6 +570,8 @@ impl TypedValueParser for BumpOptionParser {
             "major" => Ok(BumpOption::Specific(BumpType::Major)),
             "minor" => Ok(BumpOption::Specific(BumpType::Minor)),
             "patch" => Ok(BumpOption::Specific(BumpType::Patch)),
+            "prerelease" => Ok(BumpOption::Specific(BumpType::Prerelease)),
+            "release" => Ok(BumpOption::Specific(BumpType::Release)),
             _ => {
                 let mut err = clap::Error::new(ErrorKind::ValueValidation).with_cmd(cmd);
                 if let Some(arg) = arg {
@@ -490,6 +598,61 @@ impl Opt {
     fn parse_dir(dir: &str) -> Result<PathBuf, String> {
         Ok(PathBuf::from(shellexpand::tilde(dir).to_string()))
     }
+
+    /// Custom string parser for the start date of the commits.
+    fn parse_since(date: &str) -> Result<i64, String> {
+        Self::parse_date(date, false)
+    }
+
+    /// Custom string parser for the end date of the commits.
+    ///
+    /// Dates without a time include the whole day.
+    fn parse_until(date: &str) -> Result<i64, String> {
+        Self::parse_date(date, true)
+    }
+
+    /// Parses a date into a Unix timestamp.
+    ///
+    /// Accepts RFC 3339 timestamps, dates in `YYYY-MM-DD [HH:MM[:SS]]` format
+    /// in the local time zone and relative durations such as `2 weeks ago`.
+    /// Dates without a time refer to the start of the day, or to the end of
+    /// the day if `end_of_day` is set.
+    fn parse_date(value: &str, end_of_day: bool) -> Result<i64, String> {
+        let value = value.trim();
+        if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+            return Ok(date.timestamp());
+        }
+        let local_timestamp = |date: NaiveDateTime| {
+            Local
+                .from_local_datetime(&date)
+                .earliest()
+                .map(|date| date.timestamp())
+                .ok_or_else(|| format!("Invalid local date: {value}"))
+        };
+        for format in [
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%d %H:%M",
+            "%Y-%m-%dT%H:%M:%S",
+            "%Y-%m-%dT%H:%M",
+        ] {
+            if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+                return local_timestamp(date);
+            }
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            let time = if end_of_day {
+                NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN)
+            } else {
+                NaiveTime::MIN
+            };
+            return local_timestamp(date.and_time(time));
+        }
+        let duration = value.strip_suffix("ago").unwrap_or(value).trim_end();
+        let duration = humantime::parse_duration(duration)
+            .map_err(|e| format!("Invalid date `{value}`: {e}"))?;
+        let seconds = i64::try_from(duration.as_secs()).map_err(|e| e.to_string())?;
+        Ok(Local::now().timestamp() - seconds)
+    }
 }
 
 #[cfg(test)]
@@ -522,6 +685,27 @@ mod tests {
         Opt::command().debug_assert();
     }
 
+    #[test]
+    fn lint_subcommand() -> Result<(), clap::Error> {
+        let opt = Opt::try_parse_from(["git-cliff", "lint", "--message-file", "COMMIT_EDITMSG"])?;
+        match opt.command {
+            Some(Command::Lint(lint)) => {
+                assert_eq!(Some(PathBuf::from("COMMIT_EDITMSG")), lint.message_file);
+                assert_eq!(None, lint.range);
+            }
+            _ => panic!("expected the lint subcommand"),
+        }
+        let opt = Opt::try_parse_from(["git-cliff", "lint", "v1.0.0..HEAD"])?;
+        assert!(matches!(
+            opt.command,
+            Some(Command::Lint(LintOpt { range: Some(ref range), .. })) if range == "v1.0.0..HEAD"
+        ));
+        let opt = Opt::try_parse_from(["git-cliff", "v1.0.0..HEAD"])?;
+        assert!(opt.command.is_none());
+        assert_eq!(Some(String::from("v1.0.0..HEAD")), opt.range);
+        Ok(())
+    }
+
     #[test]
     fn path_tilde_expansion() {
         let home_dir = std::env::home_dir().expect("cannot retrieve home directory");
@@ -594,6 +778,14 @@ mod tests {
             BumpOption::Specific(BumpType::Major),
             bump_option_parser.parse_ref(&Opt::command(), None, OsStr::new("major"))?
         );
+        assert_eq!(
+            BumpOption::Specific(BumpType::Prerelease),
+            bump_option_parser.parse_ref(&Opt::command(), None, OsStr::new("prerelease"))?
+        );
+        assert_eq!(
+            BumpOption::Specific(BumpType::Release),
+            bump_option_parser.parse_ref(&Opt::command(), None, OsStr::new("release"))?
+        );
         Ok(())
     }
 
@@ -625,4 +817,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn parse_dates() -> Result<(), String> {
+        assert_eq!(
+            1_751_371_200,
+            Opt::parse_date("2025-07-01T12:00:00Z", false)?
+        );
+        assert_eq!(
+            Opt::parse_date("2025-07-01 00:00", false)?,
+            Opt::parse_since("2025-07-01")?
+        );
+        assert_eq!(
+            Opt::parse_date("2025-07-01 23:59:59", false)?,
+            Opt::parse_until("2025-07-01")?
+        );
+        let now = Local::now().timestamp();
+        let two_weeks_ago = Opt::parse_date("2 weeks ago", false)?;
+        assert!((now - 14 * 24 * 60 * 60 - two_weeks_ago).abs() < 60);
+        assert_eq!(
+            Opt::parse_date("2weeks", false)? / 60,
+            Opt::parse_date("2 weeks ago", false)? / 60
+        );
+        assert!(Opt::parse_date("yesterday-ish", false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn author_patterns() -> Result<(), Box<dyn std::error::Error>> {
+        let opt = Opt::try_parse_from([
+            "git-cliff",
+            "--author",
+            "Jane Doe",
+            "*@example.com",
+            "--exclude-author",
+            "bot@*",
+        ])?;
+        assert_eq!(
+            Some(vec![
+                Pattern::new("Jane Doe")?,
+                Pattern::new("*@example.com")?
+            ]),
+            opt.author
+        );
+        assert_eq!(Some(vec![Pattern::new("bot@*")?]), opt.exclude_author);
+        Ok(())
+    }
 }
@@ -16,16 +16,18 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use args::{BumpOption, Opt, Sort, Strip};
+use args::{BumpOption, LintOpt, Opt, OutputFormat, Sort, Strip, YankOpt};
 use clap::ValueEnum;
 use git_cliff_core::changelog::Changelog;
 use git_cliff_core::commit::{Commit, CommitStatistics, Range};
-use git_cliff_core::config::{CommitParser, Config};
+use git_cliff_core::config::{CommitParser, Config, ReleaseDate, TagSort, VersionSchemeType};
 use git_cliff_core::embed::{BuiltinConfig, EmbeddedConfig};
 use git_cliff_core::error::{Error, Result};
-use git_cliff_core::release::Release;
-use git_cliff_core::repo::{Repository, SubmoduleRange};
-use git_cliff_core::{DEFAULT_CONFIG, IGNORE_FILE};
+use git_cliff_core::release::{NextVersion, Release};
+use git_cliff_core::repo::{DateRange, Repository, SubmoduleRange};
+use git_cliff_core::tag::Tag;
+use git_cliff_core::version::{ReleaseLine, VersionScheme};
+use git_cliff_core::{DEFAULT_CONFIG, IGNORE_FILE, lint, manifest};
 use glob::Pattern;
 
 /// Checks for a new version on crates.io
@@ -44,6 +46,33 @@ pub fn check_new_version() {
     }
 }
 
+/// Returns the release line to include the tags of, if configured.
+///
+/// The release line is detected from the current branch if it is set to
+/// `branch`.
+fn release_line(config: &Config, repository: &Repository) -> Result<Option<ReleaseLine>> {
+    let Some(value) = &config.git.release_line else {
+        return Ok(None);
+    };
+    let release_line = if value == "branch" {
+        let branch = repository.current_branch().ok_or_else(|| {
+            Error::ChangelogError(String::from(
+                "Cannot detect the release line since HEAD is detached",
+            ))
+        })?;
+        ReleaseLine::parse(&branch).ok_or_else(|| {
+            Error::ChangelogError(format!(
+                "Cannot detect the release line from the branch: {branch}"
+            ))
+        })?
+    } else {
+        ReleaseLine::parse(value)
+            .ok_or_else(|| Error::ChangelogError(format!("Invalid release line: {value}")))?
+    };
+    tracing::debug!("Including the tags of the release line: {release_line}");
+    Ok(Some(release_line))
+}
+
 /// Produces a commit range on the format `BASE..HEAD`, derived from the
 /// command line arguments and repository tags.
 ///
@@ -53,11 +82,15 @@ fn determine_commit_range(
     config: &Config,
     repository: &Repository,
 ) -> Result<Option<String>> {
-    let tags = repository.tags(
+    let mut tags = repository.tags(
         &config.git.tag_pattern,
-        args.topo_order,
+        args.topo_order || config.git.tag_sort == TagSort::Version,
         args.use_branch_tags,
+        tag_version_scheme(config)?.as_deref(),
     )?;
+    if let Some(release_line) = release_line(config, repository)? {
+        release_line.retain_tags(&mut tags, config.bump.version_scheme()?.as_ref());
+    }
 
     let mut commit_range = args.range.clone();
     if args.unreleased {
@@ -66,7 +99,8 @@ fn determine_commit_range(
         }
     } else if args.latest || args.current {
         if tags.len() < 2 {
-            let commits = repository.commits(None, None, None, config.git.topo_order_commits)?;
+            let commits =
+                repository.commits(None, None, None, None, None, config.git.topo_order_commits)?;
             if let (Some(tag1), Some(tag2)) = (
                 commits.last().map(|c| c.id().to_string()),
                 tags.get_index(0).map(|(k, _)| k),
@@ -144,9 +178,20 @@ fn process_submodules(
                 range: range_str,
             } = submodule_range;
             let commits = sub_repo
-                .commits(Some(range_str), None, None, topo_order_commits)
+                .commits(Some(range_str), None, None, None, None, topo_order_commits)
                 .ok()
-                .map(|commits| commits.iter().map(Commit::from).collect());
+                .map(|commits| {
+                    commits
+                        .iter()
+                        .map(|git_commit| {
+                            let mut commit = Commit::from(git_commit);
+                            if let Err(e) = sub_repo.apply_mailmap(git_commit, &mut commit) {
+                                tracing::warn!("Failed to apply the mailmap: {e}");
+                            }
+                            commit
+                        })
+                        .collect()
+                });
 
             let submodule_path = sub_repo.path().to_string_lossy().into_owned();
             Some(submodule_path).zip(commits)
@@ -183,6 +228,51 @@ pub fn init_config(name: Option<&str>, config_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Records the given version as yanked in the configuration file.
+///
+/// The configuration file is resolved the same way as for generating the
+/// changelog.
+pub fn yank(mut args: Opt, yank_args: &YankOpt) -> Result<()> {
+    let version = &yank_args.version;
+    let builtin_config = BuiltinConfig::parse(args.config.to_string_lossy().to_string());
+    set_workdir(&mut args)?;
+    if args.config_url.is_some() || builtin_config.is_ok() {
+        return Err(Error::ArgumentError(String::from(
+            "Yanking is not supported for remote or built-in configurations",
+        )));
+    }
+    let Some(config_path) = resolve_config_path(&args)? else {
+        return Err(Error::ArgumentError(format!(
+            "{} is not found, use '--init' to create it",
+            args.config.display()
+        )));
+    };
+    let config_path = config_path.as_path();
+    if config_path
+        .extension()
+        .is_some_and(|extension| extension == "yaml" || extension == "yml")
+    {
+        return Err(Error::ArgumentError(String::from(
+            "Yanking is only supported for TOML configuration files",
+        )));
+    }
+    let contents = fs::read_to_string(config_path)?;
+    fs::write(config_path, Config::add_yanked_version(&contents, version)?)?;
+    tracing::info!("Recorded {version} as yanked in {}", config_path.display());
+    Ok(())
+}
+
+/// Returns the timestamp of the release of the given tag.
+///
+/// The time of the tag is used if it is configured and the tag is annotated,
+/// otherwise the time of the tagged commit.
+fn release_timestamp(config: &Config, tag: &Tag, commit_time: i64) -> i64 {
+    match (config.git.release_date, tag.timestamp) {
+        (ReleaseDate::Tag, Some(timestamp)) => timestamp,
+        _ => commit_time,
+    }
+}
+
 /// Processes the tags and commits for creating release entries for the
 /// changelog.
 ///
@@ -195,9 +285,17 @@ fn process_repository<'a>(
 ) -> Result<Vec<Release<'a>>> {
     let mut tags = repository.tags(
         &config.git.tag_pattern,
-        args.topo_order,
+        args.topo_order || config.git.tag_sort == TagSort::Version,
         args.use_branch_tags,
+        tag_version_scheme(config)?.as_deref(),
     )?;
+    let version_scheme = config.bump.version_scheme()?;
+    let release_line = release_line(config, repository)?;
+    if let Some(release_line) = release_line {
+        // Releases outside the line are skipped when processing the changelog.
+        release_line.retain_boundary_tags(&mut tags, version_scheme.as_ref());
+        config.git.release_line = Some(release_line.to_string());
+    }
     let skip_regex = config.git.skip_tags.as_ref();
     let ignore_regex = config.git.ignore_tags.as_ref();
     let count_tags = config.git.count_tags.as_ref();
@@ -311,10 +409,16 @@ fn process_repository<'a>(
     let include_path = (!include_path.is_empty()).then_some(include_path);
     let exclude_path =
         (!config.git.exclude_paths.is_empty()).then_some(config.git.exclude_paths.clone());
+    let include_author =
+        (!config.git.include_authors.is_empty()).then_some(config.git.include_authors.clone());
+    let exclude_author =
+        (!config.git.exclude_authors.is_empty()).then_some(config.git.exclude_authors.clone());
     let mut commits = repository.commits(
         commit_range.as_deref(),
         include_path,
         exclude_path,
+        include_author,
+        exclude_author,
         config.git.topo_order_commits,
     )?;
     repository.filter_git_blame_ignore_revs(&mut commits);
@@ -347,36 +451,96 @@ fn process_repository<'a>(
         }
     }
 
+    // The commits after `--until` are dropped, but the tag that follows the
+    // newest selected commit still marks the end of its release.
+    let mut until_tag_id = None;
+    if args.until.is_some() {
+        if let Some(newest) = commits
+            .first()
+            .filter(|commit| !tags.contains_key(&commit.id().to_string()))
+        {
+            if let Some(tag_commit) = repository
+                .find_descendant_tag(newest, &tags)?
+                .filter(|commit| !date_range(args, config).contains(commit))
+            {
+                until_tag_id = Some(tag_commit.id());
+                commits.insert(0, tag_commit);
+            }
+        }
+    }
+
+    // Group the commits by the releases that are ordered by version.
+    if config.git.tag_sort == TagSort::Version {
+        let indices = repository.tag_commit_indices(&tags)?;
+        commits.reverse();
+        commits.sort_by_key(|commit| {
+            let id = commit.id().to_string();
+            (
+                indices.get(&id).copied().unwrap_or(usize::MAX),
+                tags.contains_key(&id),
+            )
+        });
+        commits.reverse();
+    }
+
     // Process releases.
     let mut previous_release = Release::default();
     let mut first_processed_tag = None;
     let repository_path = repository.root_path()?.to_string_lossy().into_owned();
     for git_commit in commits.iter().rev() {
         let release = releases.last_mut().unwrap();
-        let mut commit = Commit::from(git_commit);
-        commit.statistics = match repository.commit_statistics(git_commit) {
-            Ok(statistics) => statistics,
-            Err(err)
-                if matches!(
-                    &err,
-                    Error::GitError(git_err) if git_err.message().contains("object not found")
-                ) =>
-            {
-                tracing::warn!(
-                    "Skipping diff statistics for commit {} because a Git object is missing: {err}",
-                    commit.id,
-                );
-                CommitStatistics::default()
+        if until_tag_id != Some(git_commit.id()) {
+            let mut commit = Commit::from(git_commit);
+            commit.statistics = match repository.commit_statistics(git_commit) {
+                Ok(statistics) => statistics,
+                Err(err)
+                    if matches!(
+                        &err,
+                        Error::GitError(git_err) if git_err.message().contains("object not found")
+                    ) =>
+                {
+                    tracing::warn!(
+                        "Skipping diff statistics for commit {} because a Git object is missing: \
+                         {err}",
+                        commit.id,
+                    );
+                    CommitStatistics::default()
+                }
+                Err(err) => return Err(err),
+            };
+            repository.apply_mailmap(git_commit, &mut commit)?;
+            if config.bump.rules.iter().any(|rule| !rule.paths.is_empty()) {
+                commit.changed_files = repository
+                    .commit_changed_files(git_commit)
+                    .iter()
+                    .map(|path| path.to_string_lossy().into_owned())
+                    .collect();
             }
-            Err(err) => return Err(err),
-        };
-        let commit_id = commit.id.clone();
-        release.commits.push(commit);
+            commit.set_signature(repository.commit_signature(git_commit));
+            if config.git.first_parent {
+                commit.merged_commits = repository
+                    .merged_commits(git_commit)?
+                    .iter()
+                    .rev()
+                    .map(|git_commit| {
+                        let mut commit = Commit::from(git_commit);
+                        repository.apply_mailmap(git_commit, &mut commit)?;
+                        Ok(commit)
+                    })
+                    .collect::<Result<_>>()?;
+            }
+            release.commits.push(commit);
+        }
         release.repository = Some(repository_path.clone());
-        release.commit_id = Some(commit_id);
+        release.commit_id = Some(git_commit.id().to_string());
         if let Some(tag) = tags.get(release.commit_id.as_ref().unwrap()) {
             release.version = Some(tag.name.clone());
             release.message.clone_from(&tag.message);
+            release.signed = tag.signed;
+            release.signature_type = tag.signature_type;
+            release.signer_key.clone_from(&tag.signer_key);
+            release.tagger.clone_from(&tag.tagger);
+            release.tag_timestamp = tag.timestamp;
             release.timestamp = if args.tag.as_deref() == Some(tag.name.as_str()) {
                 match tag_timestamp {
                     Some(timestamp) => Some(timestamp),
@@ -388,7 +552,7 @@ fn process_repository<'a>(
                     ),
                 }
             } else {
-                Some(git_commit.time().seconds())
+                Some(release_timestamp(config, tag, git_commit.time().seconds()))
             };
             if first_processed_tag.is_none() {
                 first_processed_tag = Some(tag);
@@ -410,6 +574,9 @@ fn process_repository<'a>(
     if args.sort == Sort::Newest {
         for release in &mut releases {
             release.commits.reverse();
+            for commit in &mut release.commits {
+                commit.merged_commits.reverse();
+            }
         }
     }
 
@@ -430,15 +597,22 @@ fn process_repository<'a>(
         .is_none()
     {
         // Get the previous tag of the first processed tag in the release loop.
+        let line_tags = tags
+            .iter()
+            .filter(|(_, tag)| {
+                release_line
+                    .is_none_or(|line| line.contains_tag(&tag.name, version_scheme.as_ref()))
+            })
+            .collect::<Vec<_>>();
         let first_tag = first_processed_tag
             .map(|tag| {
-                tags.iter()
-                    .enumerate()
-                    .find(|(_, (_, v))| v.name == tag.name)
-                    .and_then(|(i, _)| i.checked_sub(1))
-                    .and_then(|i| tags.get_index(i))
+                line_tags
+                    .iter()
+                    .position(|(_, v)| v.name == tag.name)
+                    .and_then(|i| i.checked_sub(1))
+                    .and_then(|i| line_tags.get(i).copied())
             })
-            .or_else(|| Some(tags.last()))
+            .or_else(|| Some(line_tags.last().copied()))
             .flatten();
 
         // Set the previous release if the first tag is found.
@@ -446,12 +620,16 @@ fn process_repository<'a>(
             let previous_release = Release {
                 commit_id: Some(commit_id.clone()),
                 version: Some(tag.name.clone()),
-                timestamp: Some(
+                timestamp: Some(release_timestamp(
+                    config,
+                    tag,
                     repository
                         .find_commit(commit_id)
                         .map(|v| v.time().seconds())
                         .unwrap_or_default(),
-                ),
+                )),
+                tagger: tag.tagger.clone(),
+                tag_timestamp: tag.timestamp,
                 ..Default::default()
             };
             releases[0].previous = Some(Box::new(previous_release));
@@ -490,58 +668,30 @@ fn process_repository<'a>(
     Ok(releases)
 }
 
-/// Runs `git-cliff`.
-///
-/// # Example
-///
-/// ```no_run
-/// use clap::Parser;
-/// use git_cliff::args::Opt;
-/// use git_cliff_core::error::Result;
+/// Returns the versioning scheme for sorting the tags.
 ///
-/// fn main() -> Result<()> {
-///     let args = Opt::parse();
-///     git_cliff::run(args)?;
-///     Ok(())
-/// }
-/// ```
-pub fn run<'a>(args: Opt) -> Result<Changelog<'a>> {
-    run_with_changelog_modifier(args, |_| Ok(()))
+/// The tags are only sorted by version if a versioning scheme other than
+/// semver is configured or `tag_sort` is set to `version`.
+fn tag_version_scheme(config: &Config) -> Result<Option<Box<dyn VersionScheme>>> {
+    if config.bump.scheme == VersionSchemeType::Semver && config.git.tag_sort != TagSort::Version {
+        return Ok(None);
+    }
+    config.bump.version_scheme().map(Some)
 }
 
-/// Runs `git-cliff` with a changelog modifier.
-///
-/// This is useful if you want to modify the [`Changelog`] before
-/// it's written or the context is printed (depending how git-cliff is started).
-///
-/// # Example
-///
-/// ```no_run
-/// use clap::Parser;
-/// use git_cliff::args::Opt;
-/// use git_cliff_core::error::Result;
-///
-/// fn main() -> Result<()> {
-///     let args = Opt::parse();
-///
-///     git_cliff::run_with_changelog_modifier(args, |changelog| {
-///         println!("Releases: {:?}", changelog.releases);
-///         Ok(())
-///     })?;
-///
-///     Ok(())
-/// }
-/// ```
-pub fn run_with_changelog_modifier<'a>(
-    mut args: Opt,
-    changelog_modifier: impl FnOnce(&mut Changelog) -> Result<()>,
-) -> Result<Changelog<'a>> {
-    // Retrieve the built-in configuration.
-    let builtin_config = BuiltinConfig::parse(args.config.to_string_lossy().to_string());
+/// Returns the range of dates of the commits given in the arguments.
+fn date_range(args: &Opt, config: &Config) -> DateRange {
+    DateRange {
+        since: args.since,
+        until: args.until,
+        commit_date: config.git.commit_date,
+    }
+}
 
-    // Set the working directory.
+/// Sets the working directory for the paths given in the arguments.
+fn set_workdir(args: &mut Opt) -> Result<()> {
     if let Some(ref workdir) = args.workdir {
-        args.config = workdir.join(args.config);
+        args.config = workdir.join(&args.config);
         match args.repository.as_mut() {
             Some(repository) => {
                 repository
@@ -550,10 +700,10 @@ pub fn run_with_changelog_modifier<'a>(
             }
             None => args.repository = Some(vec![workdir.clone()]),
         }
-        if let Some(changelog) = args.prepend {
+        if let Some(changelog) = args.prepend.take() {
             args.prepend = Some(workdir.join(changelog));
         }
-        if let Some(body_file) = args.body_file {
+        if let Some(body_file) = args.body_file.take() {
             args.body_file = Some(workdir.join(body_file));
         }
         // pushing an empty component force-adds a trailing path separator
@@ -562,18 +712,45 @@ pub fn run_with_changelog_modifier<'a>(
             workdir.join("").to_string_lossy().as_ref(),
         )?]);
     }
+    Ok(())
+}
 
-    // Set path for the configuration file.
+/// Returns the path of the configuration file based on the command line
+/// arguments.
+///
+/// Returns `None` if no configuration file is found.
+fn resolve_config_path(args: &Opt) -> Result<Option<PathBuf>> {
     let mut path = args.config.clone();
     if !path.exists() {
         if let Some(config_path) = Config::retrieve_user_config_path() {
             path = config_path;
         }
     }
+    if path.exists() {
+        return Ok(Some(path));
+    }
+    if let Some(manifest_path) = Config::retrieve_manifest_config_path()? {
+        return Ok(Some(manifest_path));
+    }
+    let discovered_path = env::current_dir()?
+        .ancestors()
+        .find_map(Config::retrieve_project_config_path);
+    if let Some(path) = &discovered_path {
+        tracing::info!(
+            "Using configuration from parent directory: {}",
+            path.display()
+        );
+    }
+    Ok(discovered_path)
+}
 
+/// Loads the configuration file based on the command line arguments.
+///
+/// Falls back to the default configuration if no configuration file is found.
+fn load_config(args: &Opt, builtin_config: Result<(Config, String)>) -> Result<Config> {
     // Parse the configuration file.
     // Load the default configuration if necessary.
-    let mut config = if let Some(url) = &args.config_url {
+    let config = if let Some(url) = &args.config_url {
         tracing::debug!("Using configuration file from: {url}");
         #[cfg(feature = "remote")]
         {
@@ -587,19 +764,8 @@ pub fn run_with_changelog_modifier<'a>(
     } else if let Ok((config, name)) = builtin_config {
         tracing::info!("Using built-in configuration file: {name}");
         config
-    } else if path.exists() {
+    } else if let Some(path) = resolve_config_path(args)? {
         Config::load(&path)?
-    } else if let Some(contents) = Config::read_from_manifest()? {
-        contents.parse()?
-    } else if let Some(discovered_path) = env::current_dir()?
-        .ancestors()
-        .find_map(Config::retrieve_project_config_path)
-    {
-        tracing::info!(
-            "Using configuration from parent directory: {}",
-            discovered_path.display()
-        );
-        Config::load(&discovered_path)?
     } else {
         #[allow(clippy::unnecessary_debug_formatting)]
         if !args.context {
@@ -611,6 +777,115 @@ pub fn run_with_changelog_modifier<'a>(
         EmbeddedConfig::parse()?
     };
 
+    Ok(config)
+}
+
+/// Runs `git-cliff`.
+///
+/// # Example
+///
+/// ```no_run
+/// use clap::Parser;
+/// use git_cliff::args::Opt;
+/// use git_cliff_core::error::Result;
+///
+/// fn main() -> Result<()> {
+///     let args = Opt::parse();
+///     git_cliff::run(args)?;
+///     Ok(())
+/// }
+/// ```
+pub fn run<'a>(args: Opt) -> Result<Changelog<'a>> {
+    run_with_changelog_modifier(args, |_| Ok(()))
+}
+
+/// Runs `git-cliff` with a changelog modifier.
+///
+/// This is useful if you want to modify the [`Changelog`] before
+/// it's written or the context is printed (depending how git-cliff is started).
+///
+/// # Example
+///
+/// ```no_run
+/// use clap::Parser;
+/// use git_cliff::args::Opt;
+/// use git_cliff_core::error::Result;
+///
+/// fn main() -> Result<()> {
+///     let args = Opt::parse();
+///
+///     git_cliff::run_with_changelog_modifier(args, |changelog| {
+///         println!("Releases: {:?}", changelog.releases);
+///         Ok(())
+///     })?;
+///
+///     Ok(())
+/// }
+/// ```
+pub fn run_with_changelog_modifier<'a>(
+    mut args: Opt,
+    changelog_modifier: impl FnOnce(&mut Changelog) -> Result<()>,
+) -> Result<Changelog<'a>> {
+    let config = prepare_config(&mut args)?;
+    let mut changelog = generate_changelog(&args, config)?;
+    changelog_modifier(&mut changelog)?;
+
+    Ok(changelog)
+}
+
+/// Runs `git-cliff` for each of the configured packages.
+///
+/// Returns a changelog per package in the order of the configuration, or a
+/// single changelog if there are no packages configured.
+pub fn run_packages<'a>(mut args: Opt) -> Result<Vec<Changelog<'a>>> {
+    let config = prepare_config(&mut args)?;
+    if config.packages.is_empty() || args.from_context.is_some() {
+        return Ok(vec![generate_changelog(&args, config)?]);
+    }
+    if args.output.is_some() || args.prepend.is_some() {
+        return Err(Error::ArgumentError(String::from(
+            "'-o' and '-p' cannot be used with packages, set the output of each package instead",
+        )));
+    }
+    let mut changelogs = Vec::<(String, Changelog)>::with_capacity(config.packages.len());
+    for package in config.packages_in_order()? {
+        tracing::info!("Processing package: {}", package.name);
+        let mut changelog = generate_changelog(&args, config.for_package(package))?;
+        for (name, dependency) in &changelogs {
+            if !package.dependencies.contains(name) {
+                continue;
+            }
+            if let Some(next) = dependency.next_version()? {
+                tracing::debug!("Bumping {} for its dependency {name}", package.name);
+                changelog.add_dependency_update(name, next.version);
+            }
+        }
+        changelogs.push((package.name.clone(), changelog));
+    }
+    Ok(config
+        .packages
+        .iter()
+        .filter_map(|package| {
+            changelogs
+                .iter()
+                .position(|(name, _)| name == &package.name)
+                .map(|index| changelogs.swap_remove(index).1)
+        })
+        .collect())
+}
+
+/// Loads the configuration and updates it based on the command line
+/// arguments and vice versa.
+fn prepare_config(args: &mut Opt) -> Result<Config> {
+    // Retrieve the built-in configuration.
+    let builtin_config = BuiltinConfig::parse(args.config.to_string_lossy().to_string());
+
+    // Set the working directory.
+    set_workdir(args)?;
+
+    // Parse the configuration file.
+    let mut config = load_config(args, builtin_config)?;
+
     // Update the configuration based on command line arguments and vice versa.
     let output = args.output.clone().or(config.changelog.output.clone());
     match args.strip {
@@ -634,6 +909,22 @@ pub fn run_with_changelog_modifier<'a>(
             )));
         }
     }
+    if (args.commit || args.create_tag) && args.bump.is_none() {
+        return Err(Error::ArgumentError(String::from(
+            "'--commit' and '--create-tag' can only be used with '--bump'",
+        )));
+    }
+    if args.commit &&
+        !args.write_version &&
+        output
+            .iter()
+            .chain(args.prepend.iter())
+            .all(|path| path == Path::new("-"))
+    {
+        return Err(Error::ArgumentError(String::from(
+            "'--commit' can only be used with '-o', '-p' or '--write-version'",
+        )));
+    }
     if output.is_some() && args.prepend.is_some() && output.as_ref() == args.prepend.as_ref() {
         return Err(Error::ArgumentError(String::from(
             "'-o' and '-p' can only be used together if they point to different files",
@@ -728,6 +1019,7 @@ pub fn run_with_changelog_modifier<'a>(
             .postprocessors
             .iter_mut()
             .for_each(|v| v.replace_command = None);
+        config.plugins.clear();
     }
     if args.skip_tags.is_some() {
         config.git.skip_tags.clone_from(&args.skip_tags);
@@ -745,6 +1037,12 @@ pub fn run_with_changelog_modifier<'a>(
     if args.count_tags.is_some() {
         config.git.count_tags.clone_from(&args.count_tags);
     }
+    if args.release_line.is_some() {
+        config.git.release_line.clone_from(&args.release_line);
+    }
+    if args.limit_releases.is_some() {
+        config.changelog.limit_releases = args.limit_releases;
+    }
     if let Some(include_path) = &args.include_path {
         config
             .git
@@ -757,19 +1055,40 @@ pub fn run_with_changelog_modifier<'a>(
             .exclude_paths
             .extend(exclude_path.iter().cloned());
     }
+    if let Some(author) = &args.author {
+        config.git.include_authors.extend(author.iter().cloned());
+    }
+    if let Some(exclude_author) = &args.exclude_author {
+        config
+            .git
+            .exclude_authors
+            .extend(exclude_author.iter().cloned());
+    }
 
     // Process commits and releases for the changelog.
     if let Some(BumpOption::Specific(bump_type)) = args.bump {
         config.bump.bump_type = Some(bump_type);
     }
+    if args.preid.is_some() {
+        config.bump.prerelease_identifier.clone_from(&args.preid);
+    }
 
+    Ok(config)
+}
+
+/// Generates the changelog with the given configuration.
+fn generate_changelog<'a>(args: &Opt, mut config: Config) -> Result<Changelog<'a>> {
     // Generate changelog from context.
-    let mut changelog: Changelog = if let Some(context_path) = args.from_context {
+    let changelog: Changelog = if let Some(context_path) = &args.from_context {
         let mut input: Box<dyn io::Read> = if context_path == Path::new("-") {
             Box::new(io::stdin())
         } else {
             Box::new(File::open(context_path)?)
         };
+        if config.bump.base_dir.is_none() {
+            config.bump.base_dir.clone_from(&args.workdir);
+        }
+        config.bump.load_manifest_version();
         let mut changelog = Changelog::from_context(&mut input, config)?;
         changelog.add_remote_context()?;
         changelog
@@ -787,9 +1106,25 @@ pub fn run_with_changelog_modifier<'a>(
             let cwd = env::current_dir()?;
             vec![Repository::discover(cwd)?]
         };
+        // Resolve the paths of the manifests and the version files relative to
+        // the root of the last given repository.
+        if let Some(repository) = repositories.last() {
+            let root = repository.root_path()?;
+            config.bump.base_dir = Some(match config.bump.base_dir.take() {
+                Some(path) => root.join(path),
+                None => root,
+            });
+        }
+        config.bump.load_manifest_version();
         let mut releases = Vec::<Release>::new();
         let mut commit_range = None;
-        for repository in repositories {
+        for mut repository in repositories {
+            if let Some(mailmap_file) = &config.git.mailmap_file {
+                repository.add_mailmap_file(mailmap_file)?;
+            }
+            repository.set_first_parent(config.git.first_parent);
+            repository.set_date_range(date_range(args, &config));
+
             // Skip commits
             let mut skip_list = Vec::new();
             let ignore_file = repository.root_path()?.join(IGNORE_FILE);
@@ -817,32 +1152,106 @@ pub fn run_with_changelog_modifier<'a>(
             // in the changelog, doesn't make sense if multiple repositories are
             // specified. As such, pick the commit range from the last given
             // repository.
-            commit_range = determine_commit_range(&args, &config, &repository)?;
+            commit_range = determine_commit_range(args, &config, &repository)?;
 
             releases.extend(process_repository(
                 Box::leak(Box::new(repository)),
                 &mut config,
-                &args,
+                args,
             )?);
         }
-        Changelog::new(releases, config, commit_range.as_deref())?
+        Changelog::with_commit_order(
+            releases,
+            config,
+            commit_range.as_deref(),
+            args.sort == Sort::Newest,
+        )?
     };
-    changelog_modifier(&mut changelog)?;
 
     Ok(changelog)
 }
 
+/// Release that is written to the files but not committed and tagged yet.
+struct PendingRelease {
+    /// Paths of the written files.
+    paths: Vec<PathBuf>,
+    /// Message of the release commit.
+    commit_message: Option<String>,
+    /// Name and message of the tag to create.
+    tag: Option<(String, String)>,
+    /// Whether to sign the tag.
+    sign_tag: Option<bool>,
+}
+
 /// Writes the changelog to a file.
-pub fn write_changelog<W: io::Write>(
+pub fn write_changelog<W: io::Write>(args: &Opt, changelog: Changelog<'_>, out: W) -> Result<()> {
+    write_changelogs(args, vec![(changelog, out)])
+}
+
+/// Writes the changelogs of the packages to their files.
+///
+/// The written files of all the packages are committed together and then
+/// the tag of each package is created.
+pub fn write_changelogs<W: io::Write>(
+    args: &Opt,
+    changelogs: Vec<(Changelog<'_>, W)>,
+) -> Result<()> {
+    let repository = if args.commit || args.create_tag {
+        Some(discover_repository(args)?)
+    } else {
+        None
+    };
+    if let Some(repository) = repository.as_ref().filter(|_| args.commit) {
+        if !args.allow_dirty && repository.is_index_dirty()? {
+            return Err(Error::ArgumentError(String::from(
+                "The index contains uncommitted changes, use '--allow-dirty' to commit anyway",
+            )));
+        }
+    }
+    let mut releases = Vec::with_capacity(changelogs.len());
+    for (changelog, out) in changelogs {
+        releases.extend(write_release(args, changelog, out)?);
+    }
+    let Some(repository) = repository else {
+        return Ok(());
+    };
+    let messages = releases
+        .iter()
+        .filter_map(|release| release.commit_message.as_deref())
+        .collect::<Vec<&str>>();
+    if !messages.is_empty() {
+        let paths = releases
+            .iter()
+            .flat_map(|release| release.paths.iter().cloned())
+            .collect::<Vec<PathBuf>>();
+        let message = messages.join("\n\n");
+        repository.commit_files(&paths, &message)?;
+        tracing::info!("Committed the release: {message}");
+    }
+    for release in releases {
+        if let Some((tag_name, tag_message)) = release.tag {
+            repository.create_tag(&tag_name, &tag_message, release.sign_tag)?;
+            tracing::info!("Created tag: {tag_name}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the changelog and the bumped version to the files.
+///
+/// Returns the release to commit and tag, if any.
+fn write_release<W: io::Write>(
     args: &Opt,
     mut changelog: Changelog<'_>,
     mut out: W,
-) -> Result<()> {
+) -> Result<Option<PendingRelease>> {
     let output = args
         .output
         .clone()
         .or(changelog.config.changelog.output.clone());
-    if args.bump.is_some() || args.bumped_version {
+    let mut tag_name = None;
+    if args.bump.is_some() || args.bumped_version || args.write_version {
         let current_version = changelog.releases.first().and_then(|release| {
             release.version.clone().or_else(|| {
                 release
@@ -851,23 +1260,34 @@ pub fn write_changelog<W: io::Write>(
                     .and_then(|previous| previous.version.clone())
             })
         });
-        let next_version = if let Some(next_version) = changelog.bump_version()? {
-            if current_version.as_ref() == Some(&next_version) {
+        let next = if let Some(next) = changelog.bump_version()? {
+            if current_version.as_ref() == Some(&next.version) {
                 tracing::warn!(
                     "The next version is the same as the current version, there is nothing to bump"
                 );
             }
-            next_version
+            next
         } else if let Some(last_version) =
             changelog.releases.first().cloned().and_then(|v| v.version)
         {
             tracing::warn!("There is nothing to bump");
-            last_version
+            NextVersion {
+                previous_version: Some(last_version.clone()),
+                version: last_version,
+                bump_type: None,
+                commits: Vec::new(),
+            }
         } else if changelog.releases.is_empty() {
-            changelog.config.bump.get_initial_tag()
+            NextVersion {
+                previous_version: None,
+                version: changelog.config.bump.get_initial_tag(),
+                bump_type: None,
+                commits: Vec::new(),
+            }
         } else {
-            return Ok(());
+            return Ok(None);
         };
+        let next_version = next.version.clone();
         if let Some(tag_pattern) = &changelog.config.git.tag_pattern {
             if !tag_pattern.is_match(&next_version) {
                 return Err(Error::ChangelogError(format!(
@@ -875,18 +1295,34 @@ pub fn write_changelog<W: io::Write>(
                 )));
             }
         }
+        if args.write_version {
+            let version_scheme = changelog.config.bump.version_scheme()?;
+            let version = version_scheme
+                .parse(&next_version)
+                .and_then(|version| next_version.strip_prefix(&version.prefix))
+                .unwrap_or(&next_version);
+            manifest::write_version(&changelog.config.bump.version_files(), version)?;
+        }
         if args.bumped_version {
+            let output = match args.output_format {
+                OutputFormat::Text => next_version,
+                OutputFormat::Json => next.as_json()?,
+            };
             if changelog.config.changelog.output.is_none() {
-                writeln!(out, "{next_version}")?;
+                writeln!(out, "{output}")?;
             } else {
-                writeln!(io::stdout(), "{next_version}")?;
+                writeln!(io::stdout(), "{output}")?;
             }
-            return Ok(());
+            return Ok(None);
+        }
+        // There is nothing to release if the version is already tagged.
+        if current_version.as_ref() != Some(&next_version) {
+            tag_name = Some(next_version);
         }
     }
     if args.context {
         changelog.write_context(&mut out)?;
-        return Ok(());
+        return Ok(None);
     }
     if let Some(path) = &args.prepend {
         let changelog_before = fs::read_to_string(path)?;
@@ -896,6 +1332,588 @@ pub fn write_changelog<W: io::Write>(
     if output.is_some() || args.prepend.is_none() {
         changelog.generate(&mut out)?;
     }
+    out.flush()?;
+    let archive_output = changelog
+        .config
+        .changelog
+        .archive_output
+        .clone()
+        .filter(|_| !changelog.archived_releases.is_empty());
+    if let Some(path) = &archive_output {
+        tracing::info!("Writing the archived releases to {}", path.display());
+        let mut out = io::BufWriter::new(File::create(path)?);
+        changelog.generate_archive(&mut out)?;
+        out.flush()?;
+    }
+    let Some(tag_name) = tag_name.filter(|_| args.commit || args.create_tag) else {
+        return Ok(None);
+    };
+    let mut paths = output
+        .iter()
+        .chain(args.prepend.iter())
+        .chain(archive_output.iter())
+        .filter(|path| path.as_path() != Path::new("-"))
+        .cloned()
+        .collect::<Vec<PathBuf>>();
+    if args.write_version {
+        paths.extend(
+            changelog
+                .config
+                .bump
+                .version_files()
+                .into_iter()
+                .map(|file| file.path),
+        );
+    }
+    let commit_message = if args.commit {
+        Some(changelog.commit_message()?)
+    } else {
+        None
+    };
+    let tag = if args.create_tag {
+        Some((tag_name, changelog.tag_message()?))
+    } else {
+        None
+    };
+    Ok(Some(PendingRelease {
+        paths,
+        commit_message,
+        tag,
+        sign_tag: changelog.config.bump.sign_tag,
+    }))
+}
 
+/// Contents of the commit-msg hook.
+const COMMIT_MSG_HOOK: &str = r#"#!/bin/sh
+# Installed by git-cliff: lints the commit message using the configuration.
+exec git-cliff lint --message-file "$1"
+"#;
+
+/// Lints the commit messages.
+///
+/// The commit message is either read from the given file or the commits are
+/// retrieved from the repository. If no commit range is given, the commits
+/// after the latest tag are linted.
+pub fn lint(mut args: Opt, lint_args: &LintOpt) -> Result<()> {
+    let builtin_config = BuiltinConfig::parse(args.config.to_string_lossy().to_string());
+    set_workdir(&mut args)?;
+    let mut config = load_config(&args, builtin_config)?;
+    if args.no_exec {
+        config
+            .git
+            .commit_preprocessors
+            .iter_mut()
+            .for_each(|v| v.replace_command = None);
+    }
+    if !(config.git.require_conventional || config.git.fail_on_unmatched_commit) {
+        tracing::warn!(
+            "Neither 'require_conventional' nor 'fail_on_unmatched_commit' is enabled, there is \
+             nothing to check"
+        );
+    }
+
+    let commits = if let Some(path) = &lint_args.message_file {
+        let message = lint::clean_message(&fs::read_to_string(path)?);
+        if message.is_empty() {
+            tracing::debug!("Commit message is empty, skipping");
+            return Ok(());
+        }
+        vec![Commit::new(String::new(), message)]
+    } else {
+        let path = match args.repository.as_ref().and_then(|paths| paths.first()) {
+            Some(path) => fs::canonicalize(path)?,
+            None => env::current_dir()?,
+        };
+        let mut repository = Repository::discover(path)?;
+        repository.set_date_range(date_range(&args, &config));
+        let range = match &lint_args.range {
+            Some(range) => Some(range.clone()),
+            None => repository
+                .tags(
+                    &config.git.tag_pattern,
+                    config.git.topo_order || config.git.tag_sort == TagSort::Version,
+                    config.git.use_branch_tags,
+                    tag_version_scheme(&config)?.as_deref(),
+                )?
+                .last()
+                .map(|(commit_id, _)| format!("{commit_id}..HEAD")),
+        };
+        let include_path =
+            (!config.git.include_paths.is_empty()).then(|| config.git.include_paths.clone());
+        let exclude_path =
+            (!config.git.exclude_paths.is_empty()).then(|| config.git.exclude_paths.clone());
+        let include_author =
+            (!config.git.include_authors.is_empty()).then(|| config.git.include_authors.clone());
+        let exclude_author =
+            (!config.git.exclude_authors.is_empty()).then(|| config.git.exclude_authors.clone());
+        repository
+            .commits(
+                range.as_deref(),
+                include_path,
+                exclude_path,
+                include_author,
+                exclude_author,
+                config.git.topo_order_commits,
+            )?
+            .iter()
+            .rev()
+            .map(Commit::from)
+            .collect()
+    };
+
+    let violations = lint::lint_commits(&commits, &config.git)?;
+    for violation in &violations {
+        tracing::error!("{violation}");
+    }
+    if violations.is_empty() {
+        tracing::info!("Linted {} commit(s), no violations found", commits.len());
+        Ok(())
+    } else {
+        Err(Error::LintError(violations.len()))
+    }
+}
+
+/// Discovers the (first) repository from the command line arguments.
+///
+/// Falls back to the working directory or the current directory.
+fn discover_repository(args: &Opt) -> Result<Repository> {
+    let path = match args.repository.as_ref().and_then(|paths| paths.first()) {
+        Some(path) => fs::canonicalize(path)?,
+        None => match &args.workdir {
+            Some(workdir) => fs::canonicalize(workdir)?,
+            None => env::current_dir()?,
+        },
+    };
+    Repository::discover(path)
+}
+
+/// Installs a commit-msg hook that lints the commit messages.
+pub fn install_hook(args: &Opt, force: bool) -> Result<()> {
+    let repository = discover_repository(args)?;
+    let hooks_path = repository.hooks_path()?;
+    let hook_path = hooks_path.join("commit-msg");
+    if hook_path.exists() && !force {
+        return Err(Error::ArgumentError(format!(
+            "{} already exists, use '--force' to overwrite it",
+            hook_path.display()
+        )));
+    }
+    fs::create_dir_all(&hooks_path)?;
+    fs::write(&hook_path, COMMIT_MSG_HOOK)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+    }
+    tracing::info!("Installed the commit-msg hook to {}", hook_path.display());
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use std::process::Command;
+
+    use clap::Parser;
+    use pretty_assertions::assert_eq;
+    use temp_dir::TempDir;
+
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to execute git");
+        assert!(output.status.success(), "git {args:?} failed {output:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// Creates a repository in a temporary directory.
+    ///
+    /// Returns the directory and a function for committing all the changes
+    /// with the given message and date.
+    fn test_repo() -> (TempDir, impl Fn(&str, &str)) {
+        let dir = TempDir::with_prefix("git-cliff-").expect("failed to create temp dir");
+        let path = dir.path().to_path_buf();
+        git(&path, &["init", "--initial-branch", "main"]);
+        git(&path, &["config", "user.email", "test@gmail.com"]);
+        git(&path, &["config", "user.name", "test"]);
+        git(&path, &["config", "tag.gpgSign", "false"]);
+        let commit = move |message: &str, date: &str| {
+            git(&path, &["add", "-A"]);
+            let output = Command::new("git")
+                .args(["commit", "--no-gpg-sign", "--allow-empty", "-m", message])
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .current_dir(&path)
+                .output()
+                .expect("failed to execute git");
+            assert!(output.status.success(), "git commit failed {output:?}");
+        };
+        (dir, commit)
+    }
+
+    #[test]
+    fn write_version_of_packages() -> Result<()> {
+        let (dir, commit) = test_repo();
+        let path = dir.path();
+        fs::write(
+            path.join("cliff.toml"),
+            r#"
+[changelog]
+body = "{{ version }}"
+
+[bump]
+files = [{ path = "Cargo.toml" }]
+
+[[packages]]
+name = "core"
+path = "core"
+paths = ["core/**"]
+tag_prefix = "core-v"
+
+[[packages]]
+name = "web"
+path = "web"
+paths = ["web/**"]
+tag_prefix = "web-v"
+"#,
+        )?;
+        for package in ["core", "web"] {
+            fs::create_dir(path.join(package))?;
+            fs::write(
+                path.join(package).join("Cargo.toml"),
+                format!("[package]\nname = \"{package}\"\nversion = \"0.1.0\"\n"),
+            )?;
+        }
+        commit("feat: initial commit", "2025-01-01T00:00:00Z");
+        git(path, &["tag", "core-v0.1.0"]);
+        git(path, &["tag", "web-v0.1.0"]);
+        fs::write(path.join("core").join("lib.rs"), "")?;
+        commit("feat(core): add library", "2025-01-02T00:00:00Z");
+        fs::write(path.join("web").join("index.html"), "")?;
+        commit("fix(web): add index", "2025-01-03T00:00:00Z");
+        let parent = git(path, &["rev-parse", "HEAD"]);
+
+        let args = Opt::parse_from([
+            "git-cliff",
+            "--workdir",
+            path.to_str().expect("temp dir path is valid UTF-8"),
+            "--bump",
+            "--write-version",
+            "--commit",
+            "--create-tag",
+        ]);
+        let changelogs = run_packages(args.clone())?
+            .into_iter()
+            .map(|changelog| (changelog, io::sink()))
+            .collect();
+        write_changelogs(&args, changelogs)?;
+
+        for (package, version) in [("core", "0.2.0"), ("web", "0.1.1")] {
+            assert_eq!(
+                format!("[package]\nname = \"{package}\"\nversion = \"{version}\"\n"),
+                fs::read_to_string(path.join(package).join("Cargo.toml"))?
+            );
+        }
+        assert_eq!(parent, git(path, &["rev-parse", "HEAD^"]));
+        assert_eq!(
+            "core/Cargo.toml\nweb/Cargo.toml",
+            git(path, &["show", "--name-only", "--format=", "HEAD"])
+        );
+        assert_eq!(
+            "chore(release): core-v0.2.0\n\nchore(release): web-v0.1.1",
+            git(path, &["log", "-1", "--format=%B"])
+        );
+        let head = git(path, &["rev-parse", "HEAD"]);
+        for tag in ["core-v0.2.0", "web-v0.1.1"] {
+            assert_eq!(
+                head,
+                git(path, &["rev-parse", &format!("{tag}^{{commit}}")])
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn split_releases_in_date_range() -> Result<()> {
+        let (dir, commit) = test_repo();
+        let path = dir.path();
+        fs::write(
+            path.join("cliff.toml"),
+            "[changelog]\nbody = \"\"\n\n[git]\nlimit_commits = 1\n",
+        )?;
+        for (message, date, tag) in [
+            ("feat: one", "2025-01-01T12:00:00Z", Some("v0.1.0")),
+            ("feat: two", "2025-02-01T12:00:00Z", None),
+            ("feat: three", "2025-02-10T12:00:00Z", None),
+            ("feat: four", "2025-03-01T12:00:00Z", Some("v0.2.0")),
+            ("feat: five", "2025-04-01T12:00:00Z", None),
+        ] {
+            fs::write(path.join("file.txt"), message)?;
+            commit(message, date);
+            if let Some(tag) = tag {
+                git(path, &["tag", tag]);
+            }
+        }
+
+        let config = path.join("cliff.toml");
+        let args = Opt::parse_from([
+            "git-cliff",
+            "--repository",
+            path.to_str().expect("temp dir path is valid UTF-8"),
+            "--config",
+            config.to_str().expect("config path is valid UTF-8"),
+            "--since",
+            "2025-01-15T00:00:00Z",
+            "--until",
+            "2025-02-15T00:00:00Z",
+        ]);
+        let changelog = run_packages(args)?.remove(0);
+        assert_eq!(1, changelog.releases.len());
+        let release = &changelog.releases[0];
+        assert_eq!(Some("v0.2.0"), release.version.as_deref());
+        assert_eq!(
+            vec!["feat: three"],
+            release
+                .commits
+                .iter()
+                .map(|commit| commit.message.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("v0.1.0"),
+            release
+                .previous
+                .as_ref()
+                .and_then(|previous| previous.version.as_deref())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn yank_in_workdir() -> Result<()> {
+        let (dir, _) = test_repo();
+        let config = dir.path().join("cliff.toml");
+        fs::write(&config, "[changelog]\nbody = \"\"\n")?;
+        let workdir = dir.path().to_str().expect("temp dir path is valid UTF-8");
+
+        let run_yank = |args: &[&str]| {
+            let args = Opt::parse_from(args);
+            let Some(args::Command::Yank(yank_args)) = args.command.clone() else {
+                panic!("yank subcommand is parsed");
+            };
+            yank(args, &yank_args)
+        };
+        run_yank(&["git-cliff", "-w", workdir, "yank", "v1.0.0"])?;
+        assert_eq!(
+            "[changelog]\nbody = \"\"\nyanked = ['v1\\.0\\.0']\n",
+            fs::read_to_string(&config)?
+        );
+        assert!(matches!(
+            run_yank(&[
+                "git-cliff",
+                "-w",
+                workdir,
+                "-c",
+                "keepachangelog",
+                "yank",
+                "v1.0.0"
+            ]),
+            Err(Error::ArgumentError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn commit_requires_bump_and_clean_index() -> Result<()> {
+        let (dir, commit) = test_repo();
+        let path = dir.path();
+        fs::write(path.join("cliff.toml"), "[changelog]\nbody = \"\"\n")?;
+        commit("feat: initial commit", "2025-01-01T00:00:00Z");
+        let head = git(path, &["rev-parse", "HEAD"]);
+
+        let config = path.join("cliff.toml");
+        let args = |extra: &[&str]| {
+            Opt::parse_from(
+                [
+                    "git-cliff",
+                    "--repository",
+                    path.to_str().expect("temp dir path is valid UTF-8"),
+                    "--config",
+                    config.to_str().expect("config path is valid UTF-8"),
+                    "--commit",
+                ]
+                .iter()
+                .chain(extra),
+            )
+        };
+        assert!(matches!(
+            run_packages(args(&[])),
+            Err(Error::ArgumentError(_))
+        ));
+        assert!(matches!(
+            run_packages(args(&["--create-tag"])),
+            Err(Error::ArgumentError(_))
+        ));
+
+        assert!(matches!(
+            run_packages(args(&["--bump"])),
+            Err(Error::ArgumentError(_))
+        ));
+        assert!(matches!(
+            run_packages(args(&["--bump", "-o", "-"])),
+            Err(Error::ArgumentError(_))
+        ));
+
+        fs::write(path.join("staged.txt"), "")?;
+        git(path, &["add", "staged.txt"]);
+        let output = path.join("CHANGELOG.md");
+        let args = args(&[
+            "--bump",
+            "-o",
+            output.to_str().expect("output path is valid UTF-8"),
+        ]);
+        let changelogs = run_packages(args.clone())?
+            .into_iter()
+            .map(|changelog| (changelog, io::sink()))
+            .collect();
+        assert!(matches!(
+            write_changelogs(&args, changelogs),
+            Err(Error::ArgumentError(_))
+        ));
+        assert_eq!(head, git(path, &["rev-parse", "HEAD"]));
+        Ok(())
+    }
+
+    #[test]
+    fn lists_merged_commits_under_merge_commits() -> Result<()> {
+        let (dir, commit) = test_repo();
+        let path = dir.path();
+        commit("feat: initial", "2025-01-01T00:00:00Z");
+        git(path, &["checkout", "-b", "feature"]);
+        commit("feat: one", "2025-01-02T00:00:00Z");
+        commit("fix: two", "2025-01-03T00:00:00Z");
+        git(path, &["checkout", "main"]);
+        git(path, &[
+            "merge",
+            "--no-gpg-sign",
+            "--no-ff",
+            "--no-commit",
+            "feature",
+        ]);
+        commit("feat: merge feature", "2025-01-04T00:00:00Z");
+
+        let config = path.join("cliff.toml");
+        for (sort, commits, merged) in [
+            ("oldest", ["feat: initial", "feat: merge feature"], [
+                "feat: one",
+                "fix: two",
+            ]),
+            ("newest", ["feat: merge feature", "feat: initial"], [
+                "fix: two",
+                "feat: one",
+            ]),
+        ] {
+            fs::write(
+                &config,
+                format!(
+                    "[changelog]\nbody = \"\"\n\n[git]\nfirst_parent = true\nsort_commits = \
+                     \"{sort}\"\n"
+                ),
+            )?;
+            let args = Opt::parse_from([
+                "git-cliff",
+                "--repository",
+                path.to_str().expect("temp dir path is valid UTF-8"),
+                "--config",
+                config.to_str().expect("config path is valid UTF-8"),
+            ]);
+            let changelog = run_packages(args)?.remove(0);
+            let release = &changelog.releases[0];
+            assert_eq!(
+                commits.to_vec(),
+                release
+                    .commits
+                    .iter()
+                    .map(|commit| commit.message.as_str())
+                    .collect::<Vec<_>>()
+            );
+            let merge_commit = release
+                .commits
+                .iter()
+                .find(|commit| !commit.merged_commits.is_empty())
+                .expect("merge commit should have merged commits");
+            assert_eq!(
+                merged.to_vec(),
+                merge_commit
+                    .merged_commits
+                    .iter()
+                    .map(|commit| commit.message.as_str())
+                    .collect::<Vec<_>>()
+            );
+            assert!(
+                merge_commit
+                    .merged_commits
+                    .iter()
+                    .all(|commit| commit.conv.is_none())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn skip_releases_outside_release_line() -> Result<()> {
+        let (dir, commit) = test_repo();
+        let path = dir.path();
+        let commit = |message: &str, tag: &str, date: &str| {
+            commit(message, date);
+            git(path, &["tag", tag]);
+        };
+        commit("feat: zero", "v0.1.0", "2025-01-01T00:00:00Z");
+        commit("feat: one", "v1.0.0", "2025-01-02T00:00:00Z");
+        git(path, &["checkout", "-b", "release/1.x"]);
+        commit("fix: two", "v1.0.1", "2025-01-04T00:00:00Z");
+        git(path, &["checkout", "main"]);
+        commit("feat!: three", "v2.0.0", "2025-01-03T00:00:00Z");
+        git(path, &["checkout", "release/1.x"]);
+
+        let config = path.join("cliff.toml");
+        fs::write(
+            &config,
+            "[changelog]\nbody = \"\"\n\n[git]\nrelease_line = \"branch\"\n",
+        )?;
+        let args = Opt::parse_from([
+            "git-cliff",
+            "--repository",
+            path.to_str().expect("temp dir path is valid UTF-8"),
+            "--config",
+            config.to_str().expect("config path is valid UTF-8"),
+        ]);
+        let changelog = run_packages(args)?.remove(0);
+        assert_eq!(
+            vec![
+                (Some("v1.0.1"), vec!["fix: two"], Some("v1.0.0")),
+                (Some("v1.0.0"), vec!["feat: one"], None),
+            ],
+            changelog
+                .releases
+                .iter()
+                .map(|release| (
+                    release.version.as_deref(),
+                    release
+                        .commits
+                        .iter()
+                        .map(|commit| commit.message.as_str())
+                        .collect::<Vec<_>>(),
+                    release
+                        .previous
+                        .as_ref()
+                        .and_then(|previous| previous.version.as_deref()),
+                ))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use std::path::Path;
 use std::{env, io, process};
 
 use clap::Parser;
-use git_cliff::args::Opt;
+use git_cliff::args::{Command, Opt};
 use git_cliff::{init_config, logger};
 use git_cliff_core::error::Result;
 
@@ -46,32 +46,46 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    // Generate a changelog.
-    let changelog = git_cliff::run(args.clone())?;
+    // Run the subcommand if given.
+    if let Some(command) = &args.command {
+        let result = match command {
+            Command::Lint(lint_args) => git_cliff::lint(args.clone(), lint_args),
+            Command::InstallHook { force } => git_cliff::install_hook(&args, *force),
+            Command::Yank(yank_args) => git_cliff::yank(args.clone(), yank_args),
+        };
+        if let Err(e) = result {
+            tracing::error!("{e}");
+            process::exit(1);
+        }
+        return Ok(());
+    }
 
-    // Get output destination.
-    let output = args
-        .output
-        .clone()
-        .or(changelog.config.changelog.output.clone());
-    let out: Box<dyn io::Write> = if let Some(path) = &output {
-        if path == Path::new("-") {
-            Box::new(io::stdout())
+    // Generate a changelog.
+    let mut exit_code = 0;
+    let mut changelogs = Vec::new();
+    for changelog in git_cliff::run_packages(args.clone())? {
+        // Get output destination.
+        let output = args
+            .output
+            .clone()
+            .or(changelog.config.changelog.output.clone());
+        let out: Box<dyn io::Write> = if let Some(path) = &output {
+            if path == Path::new("-") {
+                Box::new(io::stdout())
+            } else {
+                Box::new(io::BufWriter::new(File::create(path)?))
+            }
         } else {
-            Box::new(io::BufWriter::new(File::create(path)?))
-        }
-    } else {
-        Box::new(io::stdout())
-    };
+            Box::new(io::stdout())
+        };
+        changelogs.push((changelog, out));
+    }
 
-    // Write the changelog.
-    let exit_code = match git_cliff::write_changelog(&args, changelog, out) {
-        Ok(()) => 0,
-        Err(e) => {
-            tracing::error!("{e}");
-            1
-        }
-    };
+    // Write the changelogs.
+    if let Err(e) = git_cliff::write_changelogs(&args, changelogs) {
+        tracing::error!("{e}");
+        exit_code = 1;
+    }
 
     // Report the profiler if the feature is enabled
     #[cfg(feature = "profiler")]